@@ -1,4 +1,5 @@
 
+use crate::tree::AdmonitionKind;
 use crate::tree::Element;
 use crate::tree::Tag::*;
 use crate::tree::Tree;
@@ -10,41 +11,100 @@ impl ToHtml for Element {
     fn to_html(&self) -> String {
         match self.tag {
             Paragraph => paragraph(self),
-            Heading(1) => format!("<h1>{}</h1>", self.content),
+            // the document title or a level 0 section of an article
+            Heading(1) => format!("{}{}", h(1, self), element_child(self)),
             Heading(level) => head(level, self),
-            UnordereList(level) => list(level, self), 
-            EOF => "".to_owned(),
+            Part => format!(
+                "<h1{}>{}</h1>{}",
+                id_and_class("sect0", self),
+                self.content,
+                element_child(self)
+            ),
+            DiscreteHeading(level) => format!(
+                "<h{}{}>{}</h{}>",
+                level,
+                id_and_class("discrete", self),
+                self.content,
+                level
+            ),
+            UnordereList(_) | OrderedList(_) | DescriptionList(_) => {
+                list(std::slice::from_ref(self))
+            }
+            Definition => format!("<dd>{}</dd>", definition(self)),
+            Open => match self.attributes.style() {
+                Some(style @ ("abstract" | "partintro")) => {
+                    container(&format!("openblock {}", style), self)
+                }
+                _ => container("openblock", self),
+            },
+            Example => container("exampleblock", self),
+            Sidebar => container("sidebarblock", self),
+            Listing => listing(self),
+            Literal => literal(self),
+            Quote => quote(self),
+            Verse => verse(self),
+            Admonition(kind) => admonition(kind, self),
+            Table => table(self),
+            TableRow => table_row(self, "td"),
+            TableCell => table_cell(self, "td"),
+            ThematicBreak => "<hr>".to_owned(),
+            PageBreak => "<div style=\"page-break-after: always;\"></div>".to_owned(),
+            Image => image(self),
+            Video => video(self),
+            Audio => audio(self),
+            TableColumn | Comment | AttributeEntry(_) | Eof => "".to_owned(),
         }
     }
 }
 impl ToHtml for Tree {
     fn to_html(&self) -> String {
-        self.iter().map(ToHtml::to_html).collect::<String>()
+        blocks(&self.0)
     }
 }
 
+// Consecutive list items are siblings in the tree, they are grouped
+// back into a single list here unless an item starts a new list
+fn blocks(elements: &[Element]) -> String {
+    elements
+        .chunk_by(|previous, next| {
+            previous.tag.is_list_item() && previous.tag == next.tag && !next.new_list
+        })
+        .map(|chunk| match chunk[0].tag {
+            UnordereList(_) | OrderedList(_) | DescriptionList(_) => list(chunk),
+            _ => chunk[0].to_html(),
+        })
+        .collect::<String>()
+}
+
 fn head(level: usize, element: &Element) -> String {
     section(level, element)
 }
 
 fn element_child(element: &Element) -> String {
-    element
-        .children
-        .iter()
-        .map(ToHtml::to_html)
-        .collect::<String>()
+    blocks(&element.children)
 }
 
+// Special sections such as `[appendix]` or `[glossary]` have their style as a class
 fn section(level: usize, element: &Element) -> String {
+    let style = match element.attributes.style() {
+        Some(
+            style @ ("appendix" | "glossary" | "bibliography" | "preface" | "abstract" | "colophon"
+            | "dedication" | "acknowledgments" | "index"),
+        ) => format!(" {}", style),
+        _ => "".to_owned(),
+    };
     format!(
-        "<div class=\"sect{}\">{}{}</div>",
+        "<div class=\"sect{}{}{}\">{}{}</div>",
         level - 1,
-        h(level, &element),
-        element_child(&element)
+        style,
+        roles(element),
+        h(level, element),
+        element_child(element)
     )
 }
 
-fn section_body(level: usize, element: &Element) -> String {
+#[allow(dead_code)]
+fn section_body(_level: usize, element: &Element) -> String {
     format!(
         "<div class=\"sectionbody\">{}</div>",
         element_child(element)
@@ -52,34 +112,698 @@ fn section_body(level: usize, element: &Element) -> String {
 }
 
 fn h(level: usize, element: &Element) -> String {
-    let content = &element.content;
+    // section ids are generated by the parser, unless `sectids` is unset
+    let id = element
+        .attributes
+        .id()
+        .map(|id| format!(" id=\"{}\"", id))
+        .unwrap_or_default();
+    // the caption of a lettered appendix
+    let caption = element.attributes.get("caption").unwrap_or_default();
     format!(
-        "<h{} id=\"_{}\">{}</h{}>",
-        level,
-        to_snake_case(content),
-        content,
-        level
+        "<h{}{}>{}{}</h{}>",
+        level, id, caption, element.content, level
+    )
+}
+
+fn list(items: &[Element]) -> String {
+    match items[0].tag {
+        OrderedList(level) => olist(level, items),
+        DescriptionList(_) => dlist(items),
+        _ => ulist(items),
+    }
+}
+
+fn ulist(items: &[Element]) -> String {
+    if !items
+        .iter()
+        .any(|item| item.attributes.has_option("checkbox"))
+    {
+        // a `[square]` style is the class of both the block and the list
+        let (class, ul_class) = match items[0].attributes.style() {
+            Some(style) => (format!("ulist {}", style), format!(" class=\"{}\"", style)),
+            None => ("ulist".to_owned(), "".to_owned()),
+        };
+        return format!(
+            "<div{}>{}<ul{}>{}</ul></div>",
+            id_and_class(&class, &items[0]),
+            title(&items[0]),
+            ul_class,
+            items.iter().map(list_item).collect::<String>()
+        );
+    }
+
+    let interactive = items[0].attributes.has_option("interactive");
+    let checklist_item = |item: &Element| {
+        let checked = item.attributes.has_option("checked");
+        let checkbox = match (item.attributes.has_option("checkbox"), interactive) {
+            (false, _) => "".to_owned(),
+            (true, true) => format!(
+                "<input type=\"checkbox\" data-item-complete=\"{}\"{} disabled> ",
+                checked as u8,
+                if checked { " checked" } else { "" }
+            ),
+            (true, false) if checked => "&#10003; ".to_owned(),
+            (true, false) => "&#10063; ".to_owned(),
+        };
+        format!(
+            "<li><p>{}{}</p>{}</li>",
+            checkbox,
+            item.content,
+            element_child(item)
+        )
+    };
+
+    format!(
+        "<div{}>{}<ul class=\"checklist\">{}</ul></div>",
+        id_and_class("ulist checklist", &items[0]),
+        title(&items[0]),
+        items.iter().map(checklist_item).collect::<String>()
+    )
+}
+
+const NUMBERING_STYLES: [&str; 5] = [
+    "arabic",
+    "loweralpha",
+    "lowerroman",
+    "upperalpha",
+    "upperroman",
+];
+
+// The list attributes are held by its first item
+fn olist(level: usize, items: &[Element]) -> String {
+    let attributes = &items[0].attributes;
+    let style = attributes
+        .style()
+        .filter(|style| NUMBERING_STYLES.contains(style))
+        .unwrap_or(NUMBERING_STYLES[(level - 1) % NUMBERING_STYLES.len()]);
+    let numbering_type = match style {
+        "loweralpha" => " type=\"a\"",
+        "lowerroman" => " type=\"i\"",
+        "upperalpha" => " type=\"A\"",
+        "upperroman" => " type=\"I\"",
+        _ => "",
+    };
+    let start = attributes
+        .get("start")
+        .map(|start| format!(" start=\"{}\"", start))
+        .unwrap_or_default();
+    let reversed = if attributes.has_option("reversed") {
+        " reversed"
+    } else {
+        ""
+    };
+
+    format!(
+        "<div{}>{}<ol class=\"{}\"{}{}{}>{}</ol></div>",
+        id_and_class(&format!("olist {}", style), &items[0]),
+        title(&items[0]),
+        style,
+        numbering_type,
+        start,
+        reversed,
+        items.iter().map(list_item).collect::<String>()
+    )
+}
+
+fn dlist(items: &[Element]) -> String {
+    match items[0].attributes.style() {
+        Some("horizontal") => hdlist(items),
+        Some("qanda") => qlist(items),
+        style => format!(
+            "<div{}>{}<dl>{}</dl></div>",
+            id_and_class(
+                &style.map_or("dlist".to_owned(), |style| format!("dlist {}", style)),
+                &items[0]
+            ),
+            title(&items[0]),
+            items
+                .iter()
+                .map(|item| format!(
+                    "<dt class=\"hdlist1\">{}</dt>{}",
+                    item.content,
+                    element_child(item)
+                ))
+                .collect::<String>()
+        ),
+    }
+}
+
+// Terms without definition share the one of the next term
+fn description_entries(items: &[Element]) -> impl Iterator<Item = (&[Element], String)> {
+    items
+        .split_inclusive(|item| !item.children.is_empty())
+        .map(|terms| {
+            let definition = terms[terms.len() - 1]
+                .children
+                .iter()
+                .map(definition)
+                .collect::<String>();
+            (terms, definition)
+        })
+}
+
+fn hdlist(items: &[Element]) -> String {
+    let rows = description_entries(items)
+        .map(|(terms, definition)| {
+            let terms = terms
+                .iter()
+                .map(|term| term.content.as_str())
+                .collect::<Vec<&str>>()
+                .join("<br>");
+            format!(
+                "<tr><td class=\"hdlist1\">{}</td><td class=\"hdlist2\">{}</td></tr>",
+                terms, definition
+            )
+        })
+        .collect::<String>();
+
+    format!(
+        "<div{}>{}<table>{}</table></div>",
+        id_and_class("hdlist", &items[0]),
+        title(&items[0]),
+        rows
+    )
+}
+
+fn qlist(items: &[Element]) -> String {
+    let questions = description_entries(items)
+        .map(|(terms, definition)| {
+            let terms = terms
+                .iter()
+                .map(|term| format!("<p><em>{}</em></p>", term.content))
+                .collect::<String>();
+            format!("<li>{}{}</li>", terms, definition)
+        })
+        .collect::<String>();
+
+    format!(
+        "<div{}>{}<ol>{}</ol></div>",
+        id_and_class("qlist qanda", &items[0]),
+        title(&items[0]),
+        questions
+    )
+}
+
+fn definition(element: &Element) -> String {
+    let text = if element.content.is_empty() {
+        "".to_owned()
+    } else {
+        format!("<p>{}</p>", element.content)
+    };
+    format!("{}{}", text, element_child(element))
+}
+
+fn list_item(item: &Element) -> String {
+    format!("<li><p>{}</p>{}</li>", item.content, element_child(item))
+}
+
+// A sidebar holds its title within its content
+fn container(class: &str, element: &Element) -> String {
+    let (title, inner_title) = match element.tag {
+        Sidebar => ("".to_owned(), title(element)),
+        _ => (title(element), "".to_owned()),
+    };
+    format!(
+        "<div{}>{}<div class=\"content\">{}{}</div></div>",
+        id_and_class(class, element),
+        title,
+        inner_title,
+        element_child(element)
     )
 }
 
-fn list(level: usize, element: &Element) -> String {
-    // TODO 
-    "todo".to_owned()
+// Source blocks are highlighted client side from their language class
+fn listing(element: &Element) -> String {
+    let content = escape(&element.content);
+    let pre = match (
+        element.attributes.style(),
+        element.attributes.get("language"),
+    ) {
+        (_, Some(language)) => format!(
+            "<pre class=\"highlight\"><code class=\"language-{}\" data-lang=\"{}\">{}</code></pre>",
+            language, language, content
+        ),
+        (Some("source"), None) => {
+            format!("<pre class=\"highlight\"><code>{}</code></pre>", content)
+        }
+        _ => format!("<pre>{}</pre>", content),
+    };
+    format!(
+        "<div{}>{}<div class=\"content\">{}</div></div>",
+        id_and_class("listingblock", element),
+        title(element),
+        pre
+    )
+}
+
+fn literal(element: &Element) -> String {
+    format!(
+        "<div{}>{}<div class=\"content\"><pre>{}</pre></div></div>",
+        id_and_class("literalblock", element),
+        title(element),
+        escape(&element.content)
+    )
 }
 
 fn paragraph(element: &Element) -> String {
-    format!("<div class=\"paragraph\"><p>{}</p></div>", &element.content)
+    format!(
+        "<div{}>{}<p>{}</p></div>",
+        id_and_class("paragraph", element),
+        title(element),
+        line_breaks(element)
+    )
+}
+
+// A line ending with ` +` is followed by a hard line break, with the `hardbreaks`
+// option every line is
+fn line_breaks(element: &Element) -> String {
+    let hardbreaks = element.attributes.has_option("hardbreaks");
+    let lines: Vec<&str> = element.content.split('\n').collect();
+    let last = lines.len() - 1;
+    lines
+        .iter()
+        .enumerate()
+        .map(|(i, line)| match line.strip_suffix(" +") {
+            Some(line) if i < last => format!("{}<br>", line),
+            _ if hardbreaks && i < last => format!("{}<br>", line),
+            _ => line.to_string(),
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+// The `id` and `class` attributes of the outer element of a block, the roles of the
+// block are added to its class
+fn id_and_class(class: &str, element: &Element) -> String {
+    let id = element
+        .attributes
+        .id()
+        .map(|id| format!(" id=\"{}\"", id))
+        .unwrap_or_default();
+    format!("{} class=\"{}{}\"", id, class, roles(element))
 }
 
-fn to_snake_case(input: &str) -> String {
-    input.to_lowercase().replace(" ", "_")
+fn title(element: &Element) -> String {
+    title_text(element)
+        .map(|title| format!("<div class=\"title\">{}</div>", title))
+        .unwrap_or_default()
 }
+
+// The title preceded by the caption numbering the block, e.g. `Example 1. `,
+// an admonition caption is its label instead
+fn title_text(element: &Element) -> Option<String> {
+    let caption = match element.tag {
+        Admonition(_) => None,
+        _ => element.attributes.get("caption"),
+    };
+    element
+        .title
+        .as_ref()
+        .map(|title| format!("{}{}", caption.unwrap_or_default(), title))
+}
+
+fn roles(element: &Element) -> String {
+    element
+        .attributes
+        .roles()
+        .map(|role| format!(" {}", role))
+        .collect()
+}
+
+// Verbatim content is not processed but must still be valid HTML
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+fn quote(element: &Element) -> String {
+    // a quoted paragraph holds its text, a quote block its child blocks
+    let content = if element.content.is_empty() {
+        element_child(element)
+    } else {
+        line_breaks(element)
+    };
+    format!(
+        "<div{}>{}<blockquote>{}</blockquote>{}</div>",
+        id_and_class("quoteblock", element),
+        title(element),
+        content,
+        attribution(element)
+    )
+}
+
+fn verse(element: &Element) -> String {
+    format!(
+        "<div{}>{}<pre class=\"content\">{}</pre>{}</div>",
+        id_and_class("verseblock", element),
+        title(element),
+        element.content,
+        attribution(element)
+    )
+}
+
+fn admonition(kind: AdmonitionKind, element: &Element) -> String {
+    // an admonition paragraph holds its text, an admonition block its child blocks
+    let content = if element.content.is_empty() {
+        element_child(element)
+    } else {
+        line_breaks(element)
+    };
+    format!(
+        "<div{}><table><tr><td class=\"icon\"><div class=\"title\">{}</div></td><td class=\"content\">{}{}</td></tr></table></div>",
+        id_and_class(&format!("admonitionblock {}", kind.name()), element),
+        element.attributes.get("caption").unwrap_or(kind.caption()),
+        title(element),
+        content
+    )
+}
+
+// The float and alignment of an image are classes of the block, a `link` attribute
+// wraps the image in a link
+fn image(element: &Element) -> String {
+    let attributes = &element.attributes;
+    let mut class = "imageblock".to_owned();
+    if let Some(float) = attributes.get("float") {
+        class.push_str(&format!(" {}", float));
+    }
+    if let Some(align) = attributes.get("align") {
+        class.push_str(&format!(" text-{}", align));
+    }
+    let img = format!(
+        "<img src=\"{}\" alt=\"{}\"{}>",
+        element.content,
+        attributes.get("alt").unwrap_or_default(),
+        media_size(element)
+    );
+    let img = match attributes.get("link") {
+        Some(link) => format!("<a class=\"image\" href=\"{}\">{}</a>", link, img),
+        None => img,
+    };
+    format!(
+        "<div{}><div class=\"content\">{}</div>{}</div>",
+        id_and_class(&class, element),
+        img,
+        title(element)
+    )
+}
+
+// Hosted videos are embedded in an iframe, other ones played by a `<video>` element
+fn video(element: &Element) -> String {
+    let attributes = &element.attributes;
+    let size = media_size(element);
+    let content = match attributes.get("poster") {
+        Some("youtube") => {
+            let mut params = vec!["rel=0".to_owned()];
+            params.extend(
+                attributes
+                    .get("start")
+                    .map(|start| format!("start={}", start)),
+            );
+            params.extend(attributes.get("end").map(|end| format!("end={}", end)));
+            if attributes.has_option("autoplay") {
+                params.push("autoplay=1".to_owned());
+            }
+            if attributes.has_option("loop") {
+                params.push(format!("loop=1&amp;playlist={}", element.content));
+            }
+            if attributes.has_option("nocontrols") {
+                params.push("controls=0".to_owned());
+            }
+            format!(
+                "<iframe{} src=\"https://www.youtube.com/embed/{}?{}\" frameborder=\"0\" allowfullscreen></iframe>",
+                size,
+                element.content,
+                params.join("&amp;")
+            )
+        }
+        Some("vimeo") => {
+            let mut params = vec![];
+            if attributes.has_option("autoplay") {
+                params.push("autoplay=1");
+            }
+            if attributes.has_option("loop") {
+                params.push("loop=1");
+            }
+            let start = attributes
+                .get("start")
+                .map(|start| format!("#at={}", start))
+                .unwrap_or_default();
+            format!(
+                "<iframe{} src=\"https://player.vimeo.com/video/{}{}{}{}\" frameborder=\"0\" allowfullscreen></iframe>",
+                size,
+                element.content,
+                if params.is_empty() { "" } else { "?" },
+                params.join("&amp;"),
+                start
+            )
+        }
+        poster => {
+            let poster = poster
+                .map(|poster| format!(" poster=\"{}\"", poster))
+                .unwrap_or_default();
+            format!(
+                "<video src=\"{}\"{}{}{}>Your browser does not support the video tag.</video>",
+                media_source(element),
+                size,
+                poster,
+                media_options(element)
+            )
+        }
+    };
+    format!(
+        "<div{}>{}<div class=\"content\">{}</div></div>",
+        id_and_class("videoblock", element),
+        title(element),
+        content
+    )
+}
+
+fn audio(element: &Element) -> String {
+    format!(
+        "<div{}>{}<div class=\"content\"><audio src=\"{}\"{}>Your browser does not support the audio tag.</audio></div></div>",
+        id_and_class("audioblock", element),
+        title(element),
+        media_source(element),
+        media_options(element)
+    )
+}
+
+fn media_size(element: &Element) -> String {
+    ["width", "height"]
+        .iter()
+        .filter_map(|name| {
+            element
+                .attributes
+                .get(name)
+                .map(|value| format!(" {}=\"{}\"", name, value))
+        })
+        .collect()
+}
+
+// The `start` and `end` attributes select a time range with a media fragment
+fn media_source(element: &Element) -> String {
+    let attributes = &element.attributes;
+    match (attributes.get("start"), attributes.get("end")) {
+        (None, None) => element.content.clone(),
+        (start, end) => format!(
+            "{}#t={}{}",
+            element.content,
+            start.unwrap_or("0"),
+            end.map(|end| format!(",{}", end)).unwrap_or_default()
+        ),
+    }
+}
+
+fn media_options(element: &Element) -> String {
+    let attributes = &element.attributes;
+    let mut options = String::new();
+    for option in ["autoplay", "loop"] {
+        if attributes.has_option(option) {
+            options.push_str(&format!(" {}", option));
+        }
+    }
+    if !attributes.has_option("nocontrols") {
+        options.push_str(" controls");
+    }
+    options
+}
+
+// The header and footer options set whether the first and last rows are head and foot rows
+fn table(element: &Element) -> String {
+    let attributes = &element.attributes;
+    let frame = match attributes.get("frame") {
+        Some("topbot") => "ends",
+        Some(frame) => frame,
+        None => "all",
+    };
+    let grid = attributes.get("grid").unwrap_or("all");
+    let stripes = attributes
+        .get("stripes")
+        .map(|stripes| format!(" stripes-{}", stripes))
+        .unwrap_or_default();
+    let autowidth = attributes.has_option("autowidth");
+    let (width, style) = match attributes
+        .get("width")
+        .map(|width| width.trim_end_matches('%'))
+    {
+        None if autowidth => (" fit-content", "".to_owned()),
+        None | Some("100") => (" stretch", "".to_owned()),
+        Some(width) => ("", format!(" style=\"width: {}%;\"", width)),
+    };
+
+    let caption = title_text(element)
+        .map(|title| format!("<caption class=\"title\">{}</caption>", title))
+        .unwrap_or_default();
+
+    let columns = element
+        .children
+        .iter()
+        .filter(|child| child.tag == TableColumn)
+        .map(|column| match column.attributes.get("colpcwidth") {
+            Some(width) if !autowidth => format!("<col style=\"width: {}%;\">", width),
+            _ => "<col>".to_owned(),
+        })
+        .collect::<String>();
+
+    let mut rows = element
+        .children
+        .iter()
+        .filter(|child| child.tag == TableRow)
+        .collect::<Vec<&Element>>();
+    let head = match rows.first() {
+        Some(row) if attributes.has_option("header") => {
+            let head = table_row(row, "th");
+            rows.remove(0);
+            format!("<thead>{}</thead>", head)
+        }
+        _ => "".to_owned(),
+    };
+    let foot = match rows.last() {
+        Some(row) if attributes.has_option("footer") => {
+            let foot = table_row(row, "td");
+            rows.pop();
+            format!("<tfoot>{}</tfoot>", foot)
+        }
+        _ => "".to_owned(),
+    };
+    let body = if rows.is_empty() {
+        "".to_owned()
+    } else {
+        format!(
+            "<tbody>{}</tbody>",
+            rows.iter()
+                .map(|row| table_row(row, "td"))
+                .collect::<String>()
+        )
+    };
+
+    format!(
+        "<table{}{}>{}<colgroup>{}</colgroup>{}{}{}</table>",
+        id_and_class(
+            &format!(
+                "tableblock frame-{} grid-{}{}{}",
+                frame, grid, stripes, width
+            ),
+            element
+        ),
+        style,
+        caption,
+        columns,
+        head,
+        body,
+        foot
+    )
+}
+
+fn table_row(row: &Element, cell_tag: &str) -> String {
+    format!(
+        "<tr>{}</tr>",
+        row.children
+            .iter()
+            .map(|cell| table_cell(cell, cell_tag))
+            .collect::<String>()
+    )
+}
+
+// Head cells hold their text as is, body cells a paragraph for each of its paragraphs
+// unless their style makes them hold blocks
+fn table_cell(cell: &Element, cell_tag: &str) -> String {
+    let attributes = &cell.attributes;
+    let halign = attributes.get("halign").unwrap_or("left");
+    let valign = attributes.get("valign").unwrap_or("top");
+    let spans = ["colspan", "rowspan"]
+        .iter()
+        .filter_map(|name| {
+            attributes
+                .get(name)
+                .map(|span| format!(" {}=\"{}\"", name, span))
+        })
+        .collect::<String>();
+    let paragraphs = |open: &str, close: &str| {
+        cell.content
+            .split("\n\n")
+            .filter(|paragraph| !paragraph.trim().is_empty())
+            .map(|paragraph| {
+                format!(
+                    "<p class=\"tableblock\">{}{}{}</p>",
+                    open,
+                    paragraph.trim(),
+                    close
+                )
+            })
+            .collect::<String>()
+    };
+    let (cell_tag, content) = match (cell_tag, attributes.style()) {
+        ("th", _) => ("th", cell.content.clone()),
+        (_, Some("asciidoc")) => (
+            "td",
+            format!("<div class=\"content\">{}</div>", element_child(cell)),
+        ),
+        (_, Some("literal")) => (
+            "td",
+            format!(
+                "<div class=\"literal\"><pre>{}</pre></div>",
+                escape(&cell.content)
+            ),
+        ),
+        (_, Some("header")) => ("th", paragraphs("", "")),
+        (_, Some("emphasis")) => ("td", paragraphs("<em>", "</em>")),
+        (_, Some("strong")) => ("td", paragraphs("<strong>", "</strong>")),
+        (_, Some("monospaced")) => ("td", paragraphs("<code>", "</code>")),
+        _ => ("td", paragraphs("", "")),
+    };
+    format!(
+        "<{} class=\"tableblock halign-{} valign-{}\"{}>{}</{}>",
+        cell_tag, halign, valign, spans, content, cell_tag
+    )
+}
+
+fn attribution(element: &Element) -> String {
+    let author = element.attributes.get("attribution");
+    let citetitle = element.attributes.get("citetitle");
+    let credits = match (author, citetitle) {
+        (Some(author), Some(citetitle)) => {
+            format!("&#8212; {}<br><cite>{}</cite>", author, citetitle)
+        }
+        (Some(author), None) => format!("&#8212; {}", author),
+        (None, Some(citetitle)) => format!("<cite>{}</cite>", citetitle),
+        (None, None) => return "".to_owned(),
+    };
+    format!("<div class=\"attribution\">{}</div>", credits)
+}
+
 #[cfg(test)]
 mod tests {
     use crate::html::ToHtml;
 
+    use crate::tree::AdmonitionKind;
+    use crate::tree::Attributes;
     use crate::tree::Element;
 
+    use crate::tree::named;
+    use crate::tree::Tag;
     use crate::tree::Tag::*;
     use crate::tree::Tree;
 
@@ -89,9 +813,31 @@ mod tests {
             tag: Heading(1),
             content: "Hagakure Kikigaki".to_owned(),
             children: vec![],
+            ..Default::default()
+        };
+
+        assert_eq!(input.to_html(), "<h1>Hagakure Kikigaki</h1>".to_owned());
+
+        // a level 0 section of an article
+        let input = Element {
+            tag: Heading(1),
+            content: "Book One".to_owned(),
+            attributes: Attributes {
+                named: named(&[("id", "_book_one")]),
+                ..Default::default()
+            },
+            children: vec![Element {
+                tag: Paragraph,
+                content: "Chapter".to_owned(),
+                ..Default::default()
+            }],
+            ..Default::default()
         };
 
-        assert_eq!(input.to_html(), "<h1>Hagakure Kikigaki</h1>".to_owned())
+        assert_eq!(
+            input.to_html(),
+            "<h1 id=\"_book_one\">Book One</h1><div class=\"paragraph\"><p>Chapter</p></div>"
+        )
     }
 
     #[test]
@@ -99,7 +845,12 @@ mod tests {
         let input = Element {
             tag: Heading(2),
             content: "Hagakure Kikigaki".to_owned(),
+            attributes: Attributes {
+                named: vec![("id".to_owned(), "_hagakure_kikigaki".to_owned())],
+                ..Default::default()
+            },
             children: vec![],
+            ..Default::default()
         };
 
         assert_eq!(
@@ -108,6 +859,95 @@ mod tests {
         )
     }
 
+    #[test]
+    fn special_section_to_html() {
+        let section = |style: &str, pairs: &[(&str, &str)]| Element {
+            tag: Heading(2),
+            content: "Installation".to_owned(),
+            attributes: Attributes {
+                positional: vec![style.to_owned()],
+                named: named(pairs),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        assert_eq!(
+            section("appendix", &[("id", "_installation"), ("caption", "Appendix A: ")]).to_html(),
+            "<div class=\"sect1 appendix\"><h2 id=\"_installation\">Appendix A: Installation</h2></div>"
+        );
+        assert_eq!(
+            section("glossary", &[("role", "terms")]).to_html(),
+            "<div class=\"sect1 glossary terms\"><h2>Installation</h2></div>"
+        );
+        assert_eq!(
+            section("unknown", &[]).to_html(),
+            "<div class=\"sect1\"><h2>Installation</h2></div>"
+        );
+    }
+
+    #[test]
+    fn part_to_html() {
+        let input = Element {
+            tag: Part,
+            content: "Getting Started".to_owned(),
+            attributes: Attributes {
+                named: vec![("id".to_owned(), "_getting_started".to_owned())],
+                ..Default::default()
+            },
+            children: vec![
+                Element {
+                    tag: Open,
+                    attributes: Attributes {
+                        positional: vec!["partintro".to_owned()],
+                        ..Default::default()
+                    },
+                    children: vec![Element {
+                        tag: Paragraph,
+                        content: "What this part is about.".to_owned(),
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                },
+                Element {
+                    tag: Heading(2),
+                    content: "Installation".to_owned(),
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            input.to_html(),
+            "<h1 id=\"_getting_started\" class=\"sect0\">Getting Started</h1>\
+             <div class=\"openblock partintro\"><div class=\"content\"><div class=\"paragraph\"><p>What this part is about.</p></div></div></div>\
+             <div class=\"sect1\"><h2>Installation</h2></div>"
+        )
+    }
+
+    #[test]
+    fn discrete_heading_to_html() {
+        let input = Element {
+            tag: DiscreteHeading(3),
+            content: "Hagakure Kikigaki".to_owned(),
+            attributes: Attributes {
+                positional: vec!["discrete".to_owned()],
+                named: vec![
+                    ("id".to_owned(), "_hagakure_kikigaki".to_owned()),
+                    ("role".to_owned(), "lead".to_owned()),
+                ],
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        assert_eq!(
+            input.to_html(),
+            "<h3 id=\"_hagakure_kikigaki\" class=\"discrete lead\">Hagakure Kikigaki</h3>"
+        )
+    }
+
     #[test]
     fn paragraph_to_html() {
         let input = Element {
@@ -116,6 +956,7 @@ mod tests {
                       of the Way of the Samurai, it would seem that we are all negligent"
                 .to_owned(),
             children: vec![],
+            ..Default::default()
         };
 
         assert_eq!(
@@ -126,52 +967,754 @@ mod tests {
     }
 
     #[test]
-    fn document() {
-        let input = Tree {
-            0: vec![
-                Element {
-                    tag: Heading(1),
-                    content: "The message".to_owned(),
+    fn line_breaks_to_html() {
+        let paragraph = |options: &[&str]| Element {
+            tag: Paragraph,
+            content: "Roses are red, +\nviolets are blue\nsugar is sweet +".to_owned(),
+            attributes: Attributes {
+                options: options.iter().map(|option| option.to_string()).collect(),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        assert_eq!(
+            paragraph(&[]).to_html(),
+            "<div class=\"paragraph\"><p>Roses are red,<br>\nviolets are blue\nsugar is sweet +</p></div>"
+        );
+        assert_eq!(
+            paragraph(&["hardbreaks"]).to_html(),
+            "<div class=\"paragraph\"><p>Roses are red,<br>\nviolets are blue<br>\nsugar is sweet +</p></div>"
+        );
+    }
+
+    #[test]
+    fn separate_lists_to_html() {
+        let item = |content: &str| Element {
+            tag: UnordereList(1),
+            content: content.to_owned(),
+            ..Default::default()
+        };
+        let input = Tree(vec![
+            item("one"),
+            Element {
+                new_list: true,
+                ..item("two")
+            },
+        ]);
+
+        assert_eq!(
+            input.to_html(),
+            "<div class=\"ulist\"><ul><li><p>one</p></li></ul></div>\
+             <div class=\"ulist\"><ul><li><p>two</p></li></ul></div>"
+        )
+    }
+
+    #[test]
+    fn nested_list_to_html() {
+        let input = Tree(vec![
+            Element {
+                tag: UnordereList(1),
+                content: "one".to_owned(),
+                children: vec![Element {
+                    tag: UnordereList(2),
+                    content: "one.a".to_owned(),
+                    children: vec![],
+                    ..Default::default()
+                }],
+                ..Default::default()
+            },
+            Element {
+                tag: UnordereList(1),
+                content: "two".to_owned(),
+                children: vec![],
+                ..Default::default()
+            },
+        ]);
+
+        assert_eq!(
+            input.to_html(),
+            "<div class=\"ulist\"><ul>\
+             <li><p>one</p>\
+             <div class=\"ulist\"><ul><li><p>one.a</p></li></ul></div>\
+             </li>\
+             <li><p>two</p></li>\
+             </ul></div>"
+        )
+    }
+
+    #[test]
+    fn ordered_list_to_html() {
+        let input = Tree(vec![
+            Element {
+                tag: OrderedList(1),
+                content: "one".to_owned(),
+                children: vec![Element {
+                    tag: OrderedList(2),
+                    content: "one.a".to_owned(),
                     children: vec![],
+                    ..Default::default()
+                }],
+                attributes: Attributes {
+                    named: vec![("start".to_owned(), "4".to_owned())],
+                    options: vec!["reversed".to_owned()],
+                    ..Default::default()
                 },
-                // Note that any paragraph following H1 considered a "preamble" and not nested into H1 element
+                ..Default::default()
+            },
+            Element {
+                tag: OrderedList(1),
+                content: "two".to_owned(),
+                children: vec![],
+                ..Default::default()
+            },
+        ]);
+
+        assert_eq!(
+            input.to_html(),
+            "<div class=\"olist arabic\"><ol class=\"arabic\" start=\"4\" reversed>\
+             <li><p>one</p>\
+             <div class=\"olist loweralpha\"><ol class=\"loweralpha\" type=\"a\">\
+             <li><p>one.a</p></li>\
+             </ol></div>\
+             </li>\
+             <li><p>two</p></li>\
+             </ol></div>"
+        )
+    }
+
+    #[test]
+    fn checklist_to_html() {
+        let item = |content: &str, options: &[&str]| Element {
+            tag: UnordereList(1),
+            content: content.to_owned(),
+            children: vec![],
+            attributes: Attributes {
+                options: options.iter().map(|option| option.to_string()).collect(),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let input = Tree(vec![
+            item("done", &["checkbox", "checked"]),
+            item("todo", &["checkbox"]),
+            item("plain", &[]),
+        ]);
+        assert_eq!(
+            input.to_html(),
+            "<div class=\"ulist checklist\"><ul class=\"checklist\">\
+             <li><p>&#10003; done</p></li>\
+             <li><p>&#10063; todo</p></li>\
+             <li><p>plain</p></li>\
+             </ul></div>"
+        );
+
+        let input = Tree(vec![
+            item("done", &["interactive", "checkbox", "checked"]),
+            item("todo", &["checkbox"]),
+        ]);
+        assert_eq!(
+            input.to_html(),
+            "<div class=\"ulist checklist\"><ul class=\"checklist\">\
+             <li><p><input type=\"checkbox\" data-item-complete=\"1\" checked disabled> done</p></li>\
+             <li><p><input type=\"checkbox\" data-item-complete=\"0\" disabled> todo</p></li>\
+             </ul></div>"
+        );
+    }
+
+    #[test]
+    fn compound_list_item_to_html() {
+        let input = Element {
+            tag: UnordereList(1),
+            content: "install".to_owned(),
+            children: vec![
                 Element {
                     tag: Paragraph,
-                    content: "this is a story that must be told".to_owned(),
+                    content: "run the installer".to_owned(),
                     children: vec![],
+                    ..Default::default()
                 },
                 Element {
-                    tag: Heading(2),
-                    content: "Another title".to_owned(),
-                    children: vec![
-                        Element {
-                            tag: Paragraph,
+                    tag: Open,
+                    content: "".to_owned(),
+                    children: vec![Element {
+                        tag: Paragraph,
+                        content: "then reboot".to_owned(),
+                        children: vec![],
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
 
-                            content: "with nested content".to_owned(),
-                            children: vec![],
-                        },
-                        Element {
-                            tag: Heading(3),
-                            content: "And deeper nesting".to_owned(),
-                            children: vec![Element {
-                                tag: Paragraph,
-                                content: "with some content".to_owned(),
-                                children: vec![],
-                            }],
-                        },
-                    ],
+        assert_eq!(
+            input.to_html(),
+            "<div class=\"ulist\"><ul><li><p>install</p>\
+             <div class=\"paragraph\"><p>run the installer</p></div>\
+             <div class=\"openblock\"><div class=\"content\">\
+             <div class=\"paragraph\"><p>then reboot</p></div>\
+             </div></div>\
+             </li></ul></div>"
+        );
+    }
+
+    #[test]
+    fn listing_to_html() {
+        let mut input = Element {
+            tag: Listing,
+            content: "if a < b {\n    a\n}".to_owned(),
+            children: vec![],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            input.to_html(),
+            "<div class=\"listingblock\"><div class=\"content\">\
+             <pre>if a &lt; b {\n    a\n}</pre>\
+             </div></div>"
+        );
+
+        input.attributes = Attributes {
+            positional: vec!["source".to_owned(), "rust".to_owned()],
+            named: vec![("language".to_owned(), "rust".to_owned())],
+            ..Default::default()
+        };
+        assert_eq!(
+            input.to_html(),
+            "<div class=\"listingblock\"><div class=\"content\">\
+             <pre class=\"highlight\"><code class=\"language-rust\" data-lang=\"rust\">\
+             if a &lt; b {\n    a\n}\
+             </code></pre>\
+             </div></div>"
+        );
+    }
+
+    #[test]
+    fn literal_to_html() {
+        let input = Element {
+            tag: Literal,
+            content: "  <indented>".to_owned(),
+            children: vec![],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            input.to_html(),
+            "<div class=\"literalblock\"><div class=\"content\">\
+             <pre>  &lt;indented&gt;</pre>\
+             </div></div>"
+        );
+    }
+
+    #[test]
+    fn containers_to_html() {
+        let paragraph = Element {
+            tag: Paragraph,
+            content: "inside".to_owned(),
+            children: vec![],
+            ..Default::default()
+        };
+        let input = Tree(vec![
+            Element {
+                tag: Example,
+                content: "".to_owned(),
+                children: vec![paragraph.clone()],
+                ..Default::default()
+            },
+            Element {
+                tag: Sidebar,
+                content: "".to_owned(),
+                children: vec![paragraph],
+                ..Default::default()
+            },
+        ]);
+
+        assert_eq!(
+            input.to_html(),
+            "<div class=\"exampleblock\"><div class=\"content\">\
+             <div class=\"paragraph\"><p>inside</p></div>\
+             </div></div>\
+             <div class=\"sidebarblock\"><div class=\"content\">\
+             <div class=\"paragraph\"><p>inside</p></div>\
+             </div></div>"
+        );
+    }
+
+    #[test]
+    fn quotes_to_html() {
+        let mut input = Element {
+            tag: Quote,
+            content: "".to_owned(),
+            children: vec![Element {
+                tag: Paragraph,
+                content: "Never spend your money before you have it.".to_owned(),
+                children: vec![],
+                ..Default::default()
+            }],
+            attributes: Attributes {
+                named: vec![
+                    ("attribution".to_owned(), "Thomas Jefferson".to_owned()),
+                    ("citetitle".to_owned(), "Papers".to_owned()),
+                ],
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        assert_eq!(
+            input.to_html(),
+            "<div class=\"quoteblock\"><blockquote>\
+             <div class=\"paragraph\"><p>Never spend your money before you have it.</p></div>\
+             </blockquote>\
+             <div class=\"attribution\">&#8212; Thomas Jefferson<br><cite>Papers</cite></div>\
+             </div>"
+        );
+
+        input.tag = Verse;
+        input.content = "The fog comes\n  on little cat feet.".to_owned();
+        input.attributes.named.pop();
+        assert_eq!(
+            input.to_html(),
+            "<div class=\"verseblock\"><pre class=\"content\">The fog comes\n  on little cat feet.</pre>\
+             <div class=\"attribution\">&#8212; Thomas Jefferson</div>\
+             </div>"
+        );
+
+        input.tag = Quote;
+        input.content = "Quotation paragraph.".to_owned();
+        input.attributes.named.clear();
+        assert_eq!(
+            input.to_html(),
+            "<div class=\"quoteblock\"><blockquote>Quotation paragraph.</blockquote></div>"
+        );
+    }
+
+    #[test]
+    fn image_to_html() {
+        let image = |pairs: &[(&str, &str)]| Element {
+            tag: Image,
+            content: "images/sunset.jpg".to_owned(),
+            attributes: Attributes {
+                named: named(pairs),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        assert_eq!(
+            image(&[("alt", "Sunset")]).to_html(),
+            "<div class=\"imageblock\"><div class=\"content\"><img src=\"images/sunset.jpg\" alt=\"Sunset\"></div></div>"
+        );
+
+        let mut figure = image(&[
+            ("alt", "Sunset"),
+            ("width", "300"),
+            ("link", "https://example.org"),
+            ("role", "framed"),
+            ("float", "left"),
+            ("align", "center"),
+            ("caption", "Figure 1. "),
+        ]);
+        figure.title = Some("The sunset".to_owned());
+        assert_eq!(
+            figure.to_html(),
+            "<div class=\"imageblock left text-center framed\"><div class=\"content\">\
+             <a class=\"image\" href=\"https://example.org\"><img src=\"images/sunset.jpg\" alt=\"Sunset\" width=\"300\"></a>\
+             </div><div class=\"title\">Figure 1. The sunset</div></div>"
+        );
+    }
+
+    #[test]
+    fn media_to_html() {
+        let media = |tag: Tag, content: &str, pairs: &[(&str, &str)], options: &[&str]| Element {
+            tag,
+            content: content.to_owned(),
+            attributes: Attributes {
+                named: named(pairs),
+                options: options.iter().map(|option| option.to_string()).collect(),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        assert_eq!(
+            media(Video, "intro.mp4", &[("width", "640"), ("start", "10"), ("end", "20")], &["loop"]).to_html(),
+            "<div class=\"videoblock\"><div class=\"content\"><video src=\"intro.mp4#t=10,20\" width=\"640\" loop controls>\
+             Your browser does not support the video tag.</video></div></div>"
+        );
+        assert_eq!(
+            media(Video, "rPQoq7ThGAU", &[("poster", "youtube"), ("start", "30")], &["autoplay", "loop"]).to_html(),
+            "<div class=\"videoblock\"><div class=\"content\"><iframe src=\"https://www.youtube.com/embed/rPQoq7ThGAU?\
+             rel=0&amp;start=30&amp;autoplay=1&amp;loop=1&amp;playlist=rPQoq7ThGAU\" frameborder=\"0\" allowfullscreen></iframe></div></div>"
+        );
+        assert_eq!(
+            media(Video, "67480300", &[("poster", "vimeo"), ("height", "360"), ("start", "60")], &["autoplay"]).to_html(),
+            "<div class=\"videoblock\"><div class=\"content\"><iframe height=\"360\" src=\"https://player.vimeo.com/video/67480300?\
+             autoplay=1#at=60\" frameborder=\"0\" allowfullscreen></iframe></div></div>"
+        );
+
+        let mut audio = media(Audio, "ding.ogg", &[], &["nocontrols"]);
+        audio.title = Some("Notification".to_owned());
+        assert_eq!(
+            audio.to_html(),
+            "<div class=\"audioblock\"><div class=\"title\">Notification</div><div class=\"content\">\
+             <audio src=\"ding.ogg\">Your browser does not support the audio tag.</audio></div></div>"
+        );
+    }
+
+    #[test]
+    fn admonition_to_html() {
+        let mut input = Element {
+            tag: Admonition(AdmonitionKind::Warning),
+            content: "".to_owned(),
+            children: vec![Element {
+                tag: Paragraph,
+                content: "Wear a helmet.".to_owned(),
+                children: vec![],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        assert_eq!(
+            input.to_html(),
+            "<div class=\"admonitionblock warning\"><table><tr>\
+             <td class=\"icon\"><div class=\"title\">Warning</div></td>\
+             <td class=\"content\"><div class=\"paragraph\"><p>Wear a helmet.</p></div></td>\
+             </tr></table></div>"
+        );
+
+        input.tag = Admonition(AdmonitionKind::Note);
+        input.content = "Remember to bump the version".to_owned();
+        input.attributes.named = vec![("caption".to_owned(), "Heads up".to_owned())];
+        assert_eq!(
+            input.to_html(),
+            "<div class=\"admonitionblock note\"><table><tr>\
+             <td class=\"icon\"><div class=\"title\">Heads up</div></td>\
+             <td class=\"content\">Remember to bump the version</td>\
+             </tr></table></div>"
+        );
+    }
+
+    #[test]
+    fn table_to_html() {
+        let cell = |text: &str| Element {
+            tag: TableCell,
+            content: text.to_owned(),
+            ..Default::default()
+        };
+        let mut input = Element {
+            tag: Table,
+            children: vec![
+                Element {
+                    tag: TableColumn,
+                    attributes: Attributes {
+                        named: vec![("colpcwidth".to_owned(), "100".to_owned())],
+                        ..Default::default()
+                    },
+                    ..Default::default()
                 },
                 Element {
-                    tag: Heading(2),
-                    content: "Up a level".to_owned(),
+                    tag: TableRow,
+                    children: vec![cell("Name")],
+                    ..Default::default()
+                },
+                Element {
+                    tag: TableRow,
+                    children: vec![cell("first\n\nsecond")],
+                    ..Default::default()
+                },
+            ],
+            attributes: Attributes {
+                options: vec!["header".to_owned()],
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        assert_eq!(
+            input.to_html(),
+            "<table class=\"tableblock frame-all grid-all stretch\">\
+             <colgroup><col style=\"width: 100%;\"></colgroup>\
+             <thead><tr><th class=\"tableblock halign-left valign-top\">Name</th></tr></thead>\
+             <tbody><tr><td class=\"tableblock halign-left valign-top\">\
+             <p class=\"tableblock\">first</p><p class=\"tableblock\">second</p>\
+             </td></tr></tbody>\
+             </table>"
+        );
+
+        input.attributes = Attributes {
+            named: vec![
+                ("frame".to_owned(), "topbot".to_owned()),
+                ("grid".to_owned(), "rows".to_owned()),
+                ("stripes".to_owned(), "even".to_owned()),
+            ],
+            options: vec!["footer".to_owned(), "autowidth".to_owned()],
+            ..Default::default()
+        };
+        assert_eq!(
+            input.to_html(),
+            "<table class=\"tableblock frame-ends grid-rows stripes-even fit-content\">\
+             <colgroup><col></colgroup>\
+             <tbody><tr><td class=\"tableblock halign-left valign-top\"><p class=\"tableblock\">Name</p></td></tr></tbody>\
+             <tfoot><tr><td class=\"tableblock halign-left valign-top\">\
+             <p class=\"tableblock\">first</p><p class=\"tableblock\">second</p>\
+             </td></tr></tfoot>\
+             </table>"
+        );
+    }
+
+    #[test]
+    fn table_cells_to_html() {
+        let cell = |text: &str, style: &str, pairs: &[(&str, &str)]| Element {
+            tag: TableCell,
+            content: text.to_owned(),
+            attributes: Attributes {
+                positional: vec![style.to_owned()],
+                named: named(pairs),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let input = Element {
+            tag: TableRow,
+            children: vec![
+                cell("Name", "header", &[("colspan", "2"), ("rowspan", "3")]),
+                cell(
+                    "1 < 2",
+                    "literal",
+                    &[("halign", "right"), ("valign", "middle")],
+                ),
+                cell("bold", "strong", &[]),
+                Element {
                     children: vec![Element {
                         tag: Paragraph,
-                        content: "finally!".to_owned(),
+                        content: "nested".to_owned(),
                         children: vec![],
+                        ..Default::default()
                     }],
+                    ..cell("", "asciidoc", &[])
                 },
             ],
+            ..Default::default()
         };
+        assert_eq!(
+            input.to_html(),
+            "<tr>\
+             <th class=\"tableblock halign-left valign-top\" colspan=\"2\" rowspan=\"3\"><p class=\"tableblock\">Name</p></th>\
+             <td class=\"tableblock halign-right valign-middle\"><div class=\"literal\"><pre>1 &lt; 2</pre></div></td>\
+             <td class=\"tableblock halign-left valign-top\"><p class=\"tableblock\"><strong>bold</strong></p></td>\
+             <td class=\"tableblock halign-left valign-top\"><div class=\"content\"><div class=\"paragraph\"><p>nested</p></div></div></td>\
+             </tr>"
+        );
+    }
+
+    #[test]
+    fn breaks_to_html() {
+        let input = Tree(vec![
+            Element {
+                tag: ThematicBreak,
+                ..Default::default()
+            },
+            Element {
+                tag: PageBreak,
+                ..Default::default()
+            },
+        ]);
+        assert_eq!(
+            input.to_html(),
+            "<hr><div style=\"page-break-after: always;\"></div>"
+        );
+    }
+
+    #[test]
+    fn ids_and_roles_to_html() {
+        let attributes = Attributes {
+            named: vec![
+                ("id".to_owned(), "install".to_owned()),
+                ("role".to_owned(), "lead".to_owned()),
+                ("role".to_owned(), "big".to_owned()),
+            ],
+            ..Default::default()
+        };
+        let mut input = Element {
+            tag: Paragraph,
+            content: "cargo install".to_owned(),
+            children: vec![],
+            attributes: attributes.clone(),
+            ..Default::default()
+        };
+        assert_eq!(
+            input.to_html(),
+            "<div id=\"install\" class=\"paragraph lead big\"><p>cargo install</p></div>"
+        );
+
+        input.tag = UnordereList(1);
+        input.attributes.positional = vec!["square".to_owned()];
+        assert_eq!(
+            input.to_html(),
+            "<div id=\"install\" class=\"ulist square lead big\"><ul class=\"square\">\
+             <li><p>cargo install</p></li></ul></div>"
+        );
+
+        input.tag = Heading(2);
+        input.attributes = attributes;
+        assert_eq!(
+            input.to_html(),
+            "<div class=\"sect1 lead big\"><h2 id=\"install\">cargo install</h2></div>"
+        );
+    }
+
+    #[test]
+    fn block_titles_to_html() {
+        let mut input = Element {
+            tag: Example,
+            title: Some("Configuration".to_owned()),
+            attributes: Attributes {
+                named: vec![("caption".to_owned(), "Example 1. ".to_owned())],
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        assert_eq!(
+            input.to_html(),
+            "<div class=\"exampleblock\"><div class=\"title\">Example 1. Configuration</div>\
+             <div class=\"content\"></div></div>"
+        );
+
+        input.tag = Sidebar;
+        input.attributes = Attributes::default();
+        assert_eq!(
+            input.to_html(),
+            "<div class=\"sidebarblock\"><div class=\"content\">\
+             <div class=\"title\">Configuration</div></div></div>"
+        );
+
+        input.tag = Table;
+        input.attributes.named = vec![("caption".to_owned(), "Table 1. ".to_owned())];
+        assert_eq!(
+            input.to_html(),
+            "<table class=\"tableblock frame-all grid-all stretch\">\
+             <caption class=\"title\">Table 1. Configuration</caption><colgroup></colgroup></table>"
+        );
+    }
+
+    fn description_list(style: &str) -> Tree {
+        let term = |content: &str, definition: Option<&str>| Element {
+            tag: DescriptionList(1),
+            content: content.to_owned(),
+            children: definition
+                .map(|definition| Element {
+                    tag: Definition,
+                    content: definition.to_owned(),
+                    children: vec![],
+                    ..Default::default()
+                })
+                .into_iter()
+                .collect(),
+            ..Default::default()
+        };
+
+        let mut list = vec![
+            term("CPU", None),
+            term("Processor", Some("The brain")),
+            term("RAM", Some("Memory")),
+        ];
+        list[0].attributes.positional = vec![style.to_owned()];
+        Tree(list)
+    }
+
+    #[test]
+    fn description_list_to_html() {
+        assert_eq!(
+            description_list("").to_html(),
+            "<div class=\"dlist\"><dl>\
+             <dt class=\"hdlist1\">CPU</dt>\
+             <dt class=\"hdlist1\">Processor</dt><dd><p>The brain</p></dd>\
+             <dt class=\"hdlist1\">RAM</dt><dd><p>Memory</p></dd>\
+             </dl></div>"
+        );
+        assert_eq!(
+            description_list("horizontal").to_html(),
+            "<div class=\"hdlist\"><table>\
+             <tr><td class=\"hdlist1\">CPU<br>Processor</td><td class=\"hdlist2\"><p>The brain</p></td></tr>\
+             <tr><td class=\"hdlist1\">RAM</td><td class=\"hdlist2\"><p>Memory</p></td></tr>\
+             </table></div>"
+        );
+        assert_eq!(
+            description_list("qanda").to_html(),
+            "<div class=\"qlist qanda\"><ol>\
+             <li><p><em>CPU</em></p><p><em>Processor</em></p><p>The brain</p></li>\
+             <li><p><em>RAM</em></p><p>Memory</p></li>\
+             </ol></div>"
+        );
+    }
+
+    #[test]
+    fn document() {
+        let input = Tree(vec![
+            Element {
+                tag: Heading(1),
+                content: "The message".to_owned(),
+                children: vec![],
+                ..Default::default()
+            },
+            // Note that any paragraph following H1 considered a "preamble" and not nested into H1 element
+            Element {
+                tag: Paragraph,
+                content: "this is a story that must be told".to_owned(),
+                children: vec![],
+                ..Default::default()
+            },
+            Element {
+                tag: Heading(2),
+                content: "Another title".to_owned(),
+                attributes: Attributes {
+                    named: vec![("id".to_owned(), "_another_title".to_owned())],
+                    ..Default::default()
+                },
+                children: vec![
+                    Element {
+                        tag: Paragraph,
+
+                        content: "with nested content".to_owned(),
+                        children: vec![],
+                        ..Default::default()
+                    },
+                    Element {
+                        tag: Heading(3),
+                        content: "And deeper nesting".to_owned(),
+                        attributes: Attributes {
+                            named: vec![("id".to_owned(), "_and_deeper_nesting".to_owned())],
+                            ..Default::default()
+                        },
+                        children: vec![Element {
+                            tag: Paragraph,
+                            content: "with some content".to_owned(),
+                            children: vec![],
+                            ..Default::default()
+                        }],
+                        ..Default::default()
+                    },
+                ],
+                ..Default::default()
+            },
+            Element {
+                tag: Heading(2),
+                content: "Up a level".to_owned(),
+                attributes: Attributes {
+                    named: vec![("id".to_owned(), "_up_a_level".to_owned())],
+                    ..Default::default()
+                },
+                children: vec![Element {
+                    tag: Paragraph,
+                    content: "finally!".to_owned(),
+                    children: vec![],
+                    ..Default::default()
+                }],
+                ..Default::default()
+            },
+        ]);
 
         assert_eq!(
             input.to_html(),
@@ -1,3 +1,8 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+use crate::tree::AdmonitionKind;
+use crate::tree::Attributes;
 use crate::tree::Element;
 
 use crate::tree::Tag;
@@ -6,64 +11,665 @@ use crate::tree::Tree;
 
 type ParseResult<'a, Output> = Result<(&'a str, Output), &'a str>;
 
-pub fn parse(input: &str) -> ParseResult<Tree> {
+pub fn parse(input: &str) -> ParseResult<'_, Tree> {
+    let (next_input, mut tree) = parse_with_comments(input)?;
+    drop_comments(&mut tree.0);
+    Ok((next_input, tree))
+}
+
+/// Parse a document keeping its comments as `Comment` nodes, e.g. for tooling
+pub fn parse_with_comments(input: &str) -> ParseResult<'_, Tree> {
     let mut tree = Tree::new();
     let mut next_input = input;
-    if let Heading(1) = Tag::next(input) {
-        let h1 = head().parse(input).unwrap();
+    let mut doctype = "article".to_owned();
+    let mut title = None;
+    // the title may follow comments and have block attributes of its own
+    let mut comments = Tree::new();
+    let mut title_input = blank_lines().parse(input)?.0;
+    while Tag::next(title_input) == Comment {
+        let (rest, comment) = block(title_input)?;
+        comments.extend(comment);
+        title_input = blank_lines().parse(rest)?.0;
+    }
+    let (title_input, attributes) = block_attributes().parse(title_input)?;
+    let discrete = matches!(attributes.style(), Some("discrete" | "float"));
+    if let (Heading(1), false) = (Tag::next(title_input), discrete) {
+        let mut h1 = head().parse(title_input)?;
+        h1.1.attributes = attributes;
+        tree.extend(comments);
+        title = Some(tree.0.len());
         tree.push(h1.1);
-        if let Paragraph = Tag::next(h1.0) {
-            let preamble = paragraph_element().parse(h1.0).unwrap();
-            next_input = preamble.0;
-            tree.push(preamble.1);
+        next_input = h1.0;
+        // the attribute entries below the title are the document header
+        while let AttributeEntry(name) = Tag::next(next_input) {
+            let (rest, entries) = block(next_input)?;
+            if name == "doctype" {
+                doctype = entries.0[0].content.clone();
+            }
+            next_input = rest;
+            tree.extend(entries);
+        }
+    }
+    // the preamble and the sections, whose nesting depends on the doctype
+    let (next_input, mut content) = parse_elements(next_input, 0)?;
+    // the level 0 sections of a book are its parts
+    if doctype == "book" {
+        for part in content.0.iter_mut() {
+            if part.tag == Heading(1) {
+                part.tag = Part;
+                part_intro(part);
+            }
+        }
+    }
+    tree.extend(content);
+    // the title is not a section, it gets no id
+    let body = title.map_or(0, |title| title + 1);
+    Document::new().resolve(&mut tree.0[body..]);
+    Ok((next_input, tree))
+}
+
+// The blocks of a part before its first chapter are its introduction, held by a
+// `[partintro]` open block
+fn part_intro(part: &mut Element) {
+    let end = part
+        .children
+        .iter()
+        .position(|child| matches!(child.tag, Heading(_)))
+        .unwrap_or(part.children.len());
+    let is_intro = |block: &Element| block.attributes.style() == Some("partintro");
+    match &part.children[..end] {
+        [] => {}
+        [block] if block.tag == Open && is_intro(block) => {}
+        _ => {
+            let intro = Element {
+                tag: Open,
+                children: part.children.drain(..end).collect(),
+                attributes: Attributes {
+                    positional: vec!["partintro".to_owned()],
+                    ..Default::default()
+                },
+                ..Default::default()
+            };
+            part.children.insert(0, intro);
+        }
+    }
+}
+
+// Remove HTML tags, character references and the underscores of emphasized words,
+// which are word characters unlike the other inline markup characters
+fn strip_markup(title: &str) -> String {
+    let mut text = String::new();
+    let mut rest = title;
+    while let Some(c) = rest.chars().next() {
+        let tag_end = rest.find('>').filter(|_| c == '<');
+        let reference_end = rest.find(';').filter(|end| {
+            c == '&'
+                && *end > 1
+                && rest[1..*end]
+                    .chars()
+                    .all(|c| c.is_alphanumeric() || c == '#')
+        });
+        match tag_end.or(reference_end) {
+            Some(end) => rest = &rest[end + 1..],
+            None => {
+                text.push(c);
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+    }
+    text.split(' ')
+        .map(|word| word.trim_matches('_'))
+        .collect::<Vec<&str>>()
+        .join(" ")
+}
+
+// Once the comments separating two lists of the same kind are removed, the first
+// item of the second list is marked so that their items stay apart
+fn drop_comments(elements: &mut Vec<Element>) {
+    let mut previous: Option<usize> = None;
+    for index in 0..elements.len() {
+        if elements[index].tag == Comment {
+            continue;
+        }
+        if let Some(previous) = previous {
+            let tag = &elements[index].tag;
+            if previous + 1 < index && tag.is_list_item() && elements[previous].tag == *tag {
+                elements[index].new_list = true;
+            }
+        }
+        previous = Some(index);
+    }
+    elements.retain(|element| element.tag != Comment);
+    for element in elements {
+        drop_comments(&mut element.children);
+    }
+}
+
+/// The document wide state, applied to the blocks once the whole document is parsed
+struct Document {
+    attributes: HashMap<String, String>,
+    counters: HashMap<&'static str, usize>,
+    // every id used so far, explicit or generated
+    ids: HashSet<String>,
+}
+
+impl Document {
+    fn new() -> Self {
+        let defaults = [
+            ("appendix-caption", "Appendix"),
+            ("example-caption", "Example"),
+            ("figure-caption", "Figure"),
+            ("table-caption", "Table"),
+            ("sectids", ""),
+        ];
+        Document {
+            attributes: defaults
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect(),
+            counters: HashMap::new(),
+            ids: HashSet::new(),
+        }
+    }
+
+    /// Walk the blocks in document order and apply the document attributes set by
+    /// the attribute entries preceding them
+    fn resolve(&mut self, elements: &mut [Element]) {
+        for element in elements {
+            if let Some(id) = element.attributes.id() {
+                self.ids.insert(id.to_owned());
+            }
+            match &element.tag {
+                AttributeEntry(name) => match name.strip_suffix('!') {
+                    Some(name) => {
+                        self.attributes.remove(name);
+                    }
+                    None => {
+                        self.attributes
+                            .insert(name.clone(), element.content.clone());
+                    }
+                },
+                // `:note-caption:` and friends, a `caption` block attribute wins
+                Admonition(kind) if element.attributes.get("caption").is_none() => {
+                    if let Some(caption) = self.attributes.get(&format!("{}-caption", kind.name()))
+                    {
+                        let caption = ("caption".to_owned(), caption.clone());
+                        element.attributes.named.push(caption);
+                    }
+                }
+                Example | Table | Listing if element.title.is_some() => self.number(element),
+                // relative media paths are resolved against `imagesdir`
+                Image | Video | Audio => {
+                    let target = &element.content;
+                    let is_absolute = target.starts_with('/') || target.contains(':');
+                    let is_hosted =
+                        matches!(element.attributes.get("poster"), Some("youtube" | "vimeo"));
+                    match self.attributes.get("imagesdir") {
+                        Some(dir) if !dir.is_empty() && !is_absolute && !is_hosted => {
+                            element.content = format!("{}/{}", dir.trim_end_matches('/'), target);
+                        }
+                        _ => {}
+                    }
+                    if element.tag == Image && element.title.is_some() {
+                        self.number(element);
+                    }
+                }
+                Paragraph if self.attributes.contains_key("hardbreaks-option") => {
+                    element.attributes.options.push("hardbreaks".to_owned());
+                }
+                Heading(_) | DiscreteHeading(_) | Part => {
+                    if element.attributes.id().is_none() {
+                        if let Some(id) = self.section_id(&element.content) {
+                            element.attributes.named.push(("id".to_owned(), id));
+                        }
+                    }
+                    if let (Heading(_), Some("appendix")) =
+                        (&element.tag, element.attributes.style())
+                    {
+                        self.letter(element);
+                    }
+                }
+                _ => {}
+            }
+            self.resolve(&mut element.children);
+        }
+    }
+
+    /// Generate the id of a section the way Asciidoctor does: the lowercased title
+    /// without markup nor invalid characters, prefixed with `idprefix` and with spaces
+    /// replaced by `idseparator`. It's made unique with a `_2`, `_3`... suffix, and
+    /// no id is generated at all once `sectids` is unset.
+    fn section_id(&mut self, title: &str) -> Option<String> {
+        self.attributes.get("sectids")?;
+        let prefix = self.attributes.get("idprefix").map_or("_", String::as_str);
+        let separator = match self.attributes.get("idseparator") {
+            Some(separator) => separator.chars().next(),
+            None => Some('_'),
+        };
+
+        let title = strip_markup(&title.to_lowercase())
+            .chars()
+            .filter(|c| c.is_alphanumeric() || matches!(c, ' ' | '_' | '-' | '.'))
+            .collect::<String>();
+        let id = format!("{}{}", prefix, title);
+        let id = match separator {
+            None => id.replace(' ', ""),
+            Some(separator) => {
+                // runs of spaces, dots, dashes and separators become one separator
+                let replaced = match separator {
+                    '-' | '.' => " .-".to_owned(),
+                    _ => format!(" {}.-", separator),
+                };
+                let mut squeezed = String::new();
+                for c in id.chars() {
+                    if !replaced.contains(c) {
+                        squeezed.push(c);
+                    } else if !squeezed.ends_with(separator) {
+                        squeezed.push(separator);
+                    }
+                }
+                let squeezed = squeezed.strip_suffix(separator).unwrap_or(&squeezed);
+                match squeezed.strip_prefix(separator) {
+                    Some(rest) if prefix.is_empty() => rest.to_owned(),
+                    _ => squeezed.to_owned(),
+                }
+            }
+        };
+
+        let separator = separator.map(String::from).unwrap_or_default();
+        let id = (1..)
+            .map(|count| match count {
+                1 => id.clone(),
+                _ => format!("{}{}{}", id, separator, count),
+            })
+            .find(|candidate| !self.ids.contains(candidate))
+            .unwrap_or(id);
+        self.ids.insert(id.clone());
+        Some(id)
+    }
+
+    // Appendices are lettered, `Appendix A: ` from `:appendix-caption:` or `A. ` once
+    // it is unset
+    fn letter(&mut self, element: &mut Element) {
+        let number = self.counters.entry("appendix").or_default();
+        *number += 1;
+        let letter = (b'A' + (*number - 1) as u8 % 26) as char;
+        let caption = match self.attributes.get("appendix-caption") {
+            Some(label) => format!("{} {}: ", label, letter),
+            None => format!("{}. ", letter),
+        };
+        element
+            .attributes
+            .named
+            .push(("caption".to_owned(), caption));
+    }
+
+    // Titled blocks are numbered by kind, e.g. `Example 1. ` from `:example-caption:`,
+    // an explicit `caption` attribute is used as is
+    fn number(&mut self, element: &mut Element) {
+        let kind = match element.tag {
+            Example => "example",
+            Table => "table",
+            Image => "figure",
+            _ => "listing",
+        };
+        if element.attributes.get("caption").is_some() {
+            return;
+        }
+        if let Some(label) = self.attributes.get(&format!("{}-caption", kind)) {
+            let number = self.counters.entry(kind).or_default();
+            *number += 1;
+            let caption = format!("{} {}. ", label, number);
+            element
+                .attributes
+                .named
+                .push(("caption".to_owned(), caption));
         }
     }
-    let content = parse_elements(next_input, 1).unwrap();
-    tree.extend(content.1);
-    Ok((content.0, tree))
 }
-fn parse_elements(input: &str, depth: usize) -> ParseResult<Tree> {
+/// Parse the blocks of a section of the given level, 0 for the whole document or the
+/// content of a block, until a heading of the same or a lower level.
+fn parse_elements(input: &str, depth: usize) -> ParseResult<'_, Tree> {
     let mut output = Tree::new();
-    let mut next_tag = Tag::next(input);
 
-    let mut depth = depth;
     let mut next_input = input;
 
-    while next_tag != EOF {
-        match next_tag {
-            Heading(level) if level < depth => return Ok((next_input, output)),
-            Heading(level) => {
-                let mut element = head().parse(next_input).unwrap();
-                let inner = parse_elements(element.0, level).unwrap();
+    loop {
+        next_input = blank_lines().parse(next_input)?.0;
+        let (block_input, attributes) = block_attributes().parse(next_input)?;
+        // discrete headings are blocks, they neither nest nor end the current section
+        let discrete = matches!(attributes.style(), Some("discrete" | "float"));
+        match Tag::next(block_input) {
+            Heading(level) if level <= depth && !discrete => return Ok((next_input, output)),
+            Heading(level) if !discrete => {
+                let mut element = head().parse(block_input)?;
+                let inner = parse_elements(element.0, level)?;
                 element.1.set_child(inner.1);
+                element.1.attributes = attributes;
                 next_input = inner.0;
                 output.push(element.1);
-                depth = level;
-            }
-            UnordereList(level) => {
-                let ul = list().parse(next_input).unwrap();
-                let mut element: Element = ul.1;
-                next_input = ul.0;
-                while let UnordereList(next_level) = Tag::next(ul.0) {
-                    let child = list().parse(next_input).unwrap();
-                    next_input = child.0;
-                    element.children.push(child.1)
+            }
+            Eof => return Ok((block_input, output)),
+            _ => {
+                let (rest, mut blocks) = block(next_input)?;
+                // a list with attribute lines of its own is not part of the list above
+                if let (Some(previous), Some(first)) = (output.0.last(), blocks.0.first_mut()) {
+                    if first.tag == previous.tag && first.tag.is_list_item() {
+                        first.new_list = block_input != next_input;
+                    }
                 }
+                next_input = rest;
+                output.extend(blocks);
             }
-            Paragraph => {
-                while let Paragraph = next_tag {
-                    let paragraph = paragraph_element().parse(next_input).unwrap();
-                    next_input = paragraph.0;
-                    output.push(paragraph.1);
-                    next_tag = Tag::next(next_input);
+        }
+    }
+}
+
+/// Parse the next block along with its attribute lines, a list is returned
+/// as the sequence of its items.
+fn block(input: &str) -> ParseResult<'_, Tree> {
+    let (block_input, attributes) = block_attributes().parse(input)?;
+    let (next_input, mut blocks) = match Tag::next(block_input) {
+        tag @ (UnordereList(_) | OrderedList(_) | DescriptionList(_)) => {
+            list_items(block_input, &[tag])?
+        }
+        // containers hold blocks parsed with the same grammar as the document
+        tag @ (Open | Example | Sidebar) => {
+            let (next_input, content) = delimited_block(block_input)?;
+            // `[WARNING]` turns the block into an admonition
+            let tag = match attributes.style().and_then(AdmonitionKind::from_label) {
+                Some(kind) if tag != Sidebar => Admonition(kind),
+                _ => tag,
+            };
+            let mut container = Element {
+                tag,
+                ..Default::default()
+            };
+            container.set_child(parse_elements(content, 0)?.1);
+            (next_input, Tree(vec![container]))
+        }
+        Quote if block_input.starts_with('>') => {
+            let (next_input, quote) = markdown_quote(block_input)?;
+            (next_input, Tree(vec![quote]))
+        }
+        // a `[verse]` keeps the quoted lines as they are
+        Quote if attributes.style() == Some("verse") => {
+            let (next_input, content) = delimited_block(block_input)?;
+            let verse = Element {
+                tag: Verse,
+                content: content.to_owned(),
+                ..Default::default()
+            };
+            (next_input, Tree(vec![verse]))
+        }
+        Quote => {
+            let (next_input, content) = delimited_block(block_input)?;
+            let mut quote = Element {
+                tag: Quote,
+                ..Default::default()
+            };
+            quote.set_child(parse_elements(content, 0)?.1);
+            (next_input, Tree(vec![quote]))
+        }
+        Listing => {
+            let (next_input, content) = delimited_block(block_input)?;
+            let listing = Element {
+                tag: Listing,
+                content: content.to_owned(),
+                ..Default::default()
+            };
+            (next_input, Tree(vec![listing]))
+        }
+        // indented lines are literal paragraphs, otherwise it's a `....` block
+        Literal if !block_input.starts_with([' ', '\t']) => {
+            let (next_input, content) = delimited_block(block_input)?;
+            let literal = Element {
+                tag: Literal,
+                content: content.to_owned(),
+                ..Default::default()
+            };
+            (next_input, Tree(vec![literal]))
+        }
+        Literal => {
+            let (next_input, literal) = literal_paragraph(block_input)?;
+            (next_input, Tree(vec![literal]))
+        }
+        Paragraph => {
+            let (next_input, mut paragraph) = paragraph_element().parse(block_input)?;
+            match attributes.style() {
+                Some("source" | "listing") => paragraph.tag = Listing,
+                Some("literal") => paragraph.tag = Literal,
+                Some("quote") => paragraph.tag = Quote,
+                Some("verse") => paragraph.tag = Verse,
+                style => {
+                    if let Some(kind) = style.and_then(AdmonitionKind::from_label) {
+                        paragraph.tag = Admonition(kind);
+                    }
                 }
             }
-            EOF => break,
+            (next_input, Tree(vec![paragraph]))
+        }
+        // `NOTE: text`, the label is replaced by the admonition kind
+        Admonition(kind) => {
+            let (next_input, mut admonition) = paragraph_element().parse(block_input)?;
+            let (_, text) = admonition.content.split_once(": ").ok_or(input)?;
+            admonition.content = text.trim_start().to_owned();
+            admonition.tag = Admonition(kind);
+            (next_input, Tree(vec![admonition]))
+        }
+        AttributeEntry(name) => {
+            let (next_input, entry) = left(line(), blank_lines()).parse(block_input)?;
+            let (_, _, value) = Tag::attribute_entry(entry.trim_end()).ok_or(input)?;
+            let entry = Element {
+                tag: AttributeEntry(name),
+                content: value.to_owned(),
+                ..Default::default()
+            };
+            (next_input, Tree(vec![entry]))
+        }
+        // `// text` or a `////` block, whose content is kept verbatim
+        Comment if block_input.starts_with("////") => {
+            let (next_input, content) = delimited_block(block_input)?;
+            let comment = Element {
+                tag: Comment,
+                content: content.to_owned(),
+                ..Default::default()
+            };
+            (next_input, Tree(vec![comment]))
+        }
+        Comment => {
+            let (next_input, text) = left(line(), blank_lines()).parse(block_input)?;
+            let comment = Element {
+                tag: Comment,
+                content: text[2..].trim().to_owned(),
+                ..Default::default()
+            };
+            (next_input, Tree(vec![comment]))
+        }
+        // `image::target[alt, width, height]`, the alt text defaults to the file name
+        Image => {
+            let (next_input, line) = left(line(), blank_lines()).parse(block_input)?;
+            let mut image = block_macro(Image, &line, &["alt", "width", "height"]).ok_or(input)?;
+            if image.attributes.get("alt").is_none() {
+                let file = image.content.rsplit('/').next().unwrap_or_default();
+                let stem = file.rsplit_once('.').map_or(file, |(stem, _)| stem);
+                let alt = stem.replace(['-', '_'], " ");
+                image.attributes.named.insert(0, ("alt".to_owned(), alt));
+            }
+            (next_input, Tree(vec![image]))
+        }
+        // `video::target[poster, width, height]`, a `youtube` or `vimeo` poster makes the
+        // target the id of a hosted video
+        tag @ (Video | Audio) => {
+            let (next_input, line) = left(line(), blank_lines()).parse(block_input)?;
+            let names: &[&str] = match tag {
+                Video => &["poster", "width", "height"],
+                _ => &[],
+            };
+            let media = block_macro(tag, &line, names).ok_or(input)?;
+            (next_input, Tree(vec![media]))
+        }
+        tag @ (ThematicBreak | PageBreak) => {
+            let (next_input, _) = left(line(), blank_lines()).parse(block_input)?;
+            let break_element = Element {
+                tag,
+                ..Default::default()
+            };
+            (next_input, Tree(vec![break_element]))
+        }
+        Table => {
+            let (next_input, content) = delimited_block(block_input)?;
+            // `,===` and `:===` are shorthands for the csv and dsv formats
+            let format = match block_input.chars().next() {
+                Some(',') => "csv",
+                Some(':') => "dsv",
+                _ => "psv",
+            };
+            let format = attributes.get("format").unwrap_or(format);
+            (next_input, Tree(vec![table(content, format, &attributes)]))
+        }
+        Heading(level) if matches!(attributes.style(), Some("discrete" | "float")) => {
+            let (next_input, mut heading) = head().parse(block_input)?;
+            heading.tag = DiscreteHeading(level);
+            (next_input, Tree(vec![heading]))
+        }
+        Heading(_) | DiscreteHeading(_) | Part | Definition | Verse | TableColumn | TableRow
+        | TableCell | Eof => return Err(input),
+    };
+
+    // list attributes are held by their first item
+    if let Some(first) = blocks.0.first_mut() {
+        first.attributes.merge(attributes);
+        // `.Title` lines and the `title` attribute give the block title
+        if let Some(title) = first.attributes.get("title").map(String::from) {
+            first.title = Some(title);
+            first.attributes.named.retain(|(name, _)| name != "title");
+        }
+        // `[source,rust,linenums]` gives the language of a source block
+        if let (Listing, Some("source")) = (&first.tag, first.attributes.style()) {
+            if let Some(language) = first.attributes.positional.get(1).cloned() {
+                first
+                    .attributes
+                    .named
+                    .push(("language".to_owned(), language));
+            }
+            if first.attributes.positional.get(2).map(String::as_str) == Some("linenums") {
+                first.attributes.options.push("linenums".to_owned());
+            }
+        }
+        // `[quote, author, citetitle]`
+        if let Quote | Verse = first.tag {
+            let names = ["attribution", "citetitle"];
+            let positional = first.attributes.positional.iter().skip(1);
+            let credits = names
+                .iter()
+                .zip(positional)
+                .filter(|(_, value)| !value.is_empty())
+                .map(|(name, value)| (name.to_string(), value.clone()))
+                .collect::<Vec<(String, String)>>();
+            first.attributes.named.extend(credits);
+        }
+    }
+    Ok((next_input, blocks))
+}
+
+// A block macro line, its target is the content of the element and its positional
+// attributes are named after `names`
+fn block_macro(tag: Tag, line: &str, names: &[&str]) -> Option<Element> {
+    let (_, target, list) = Tag::block_macro(line.trim_end())?;
+    // the first positional attribute of a macro is not a block style with shorthands,
+    // so the list is parsed behind an empty style
+    let list = format!(",{}", list);
+    let mut attributes = attribute_list()
+        .parse(&list)
+        .map(|(_, attributes)| attributes)
+        .unwrap_or_default();
+    let positional = std::mem::take(&mut attributes.positional);
+    let named = names
+        .iter()
+        .zip(positional.into_iter().skip(1))
+        .filter(|(_, value)| !value.is_empty())
+        .map(|(name, value)| (name.to_string(), value));
+    // named attributes given explicitly win over positional ones
+    attributes.named.splice(0..0, named);
+    Some(Element {
+        tag,
+        content: target.to_owned(),
+        attributes,
+        ..Default::default()
+    })
+}
+
+/// Parse a block enclosed in two identical delimiter lines and return its raw content,
+/// an unterminated block runs until the end of the document.
+fn delimited_block(input: &str) -> ParseResult<'_, &str> {
+    let (next_input, delimiter) = line().parse(input)?;
+    let content = optional(new_line()).parse(next_input)?.0;
+
+    let mut offset = 0;
+    for line in content.split_inclusive('\n') {
+        if line.trim_end() == delimiter.trim_end() {
+            let next_input = blank_lines().parse(&content[offset + line.len()..])?.0;
+            let content = &content[..offset];
+            let content = content.strip_suffix('\n').unwrap_or(content);
+            return Ok((next_input, content.strip_suffix('\r').unwrap_or(content)));
         }
-        next_tag = Tag::next(next_input);
+        offset += line.len();
     }
+    Ok(("", content))
+}
 
-    Ok((next_input, output))
+/// Parse consecutive list items sharing the last tag of `ancestors`, items with a
+/// marker we have not seen yet are nested into the children of the preceding item.
+fn list_items<'a>(input: &'a str, ancestors: &[Tag]) -> ParseResult<'a, Tree> {
+    let mut items = Tree::new();
+    let mut next_input = input;
+    let tag = &ancestors[ancestors.len() - 1];
+
+    loop {
+        match Tag::next(next_input) {
+            next_tag if next_tag == *tag => {
+                let mut item = match tag {
+                    DescriptionList(_) => description_item(next_input)?,
+                    _ => list().parse(next_input)?,
+                };
+                // An explicit first ordinal such as `4.` sets where the numbering starts
+                if let (true, Ok((_, (_, Some(ordinal))))) =
+                    (items.0.is_empty(), list_marker().parse(next_input))
+                {
+                    if ordinal != 1 {
+                        let start = ("start".to_owned(), ordinal.to_string());
+                        item.1.attributes.named.push(start);
+                    }
+                }
+                next_input = item.0;
+
+                while let Ok((attached_input, _)) = list_continuation().parse(next_input) {
+                    match block(attached_input) {
+                        Ok((rest, attached)) => {
+                            next_input = rest;
+                            item.1.attach(attached);
+                        }
+                        Err(_) => break,
+                    }
+                }
+                items.push(item.1);
+            }
+            next_tag
+                if next_tag.is_list_item()
+                    && !ancestors.contains(&next_tag)
+                    && !items.0.is_empty() =>
+            {
+                let nested = list_items(next_input, &[ancestors, &[next_tag]].concat())?;
+                next_input = nested.0;
+                if let Some(parent) = items.0.last_mut() {
+                    parent.attach(nested.1);
+                }
+            }
+            _ => return Ok((next_input, items)),
+        }
+        next_input = blank_lines().parse(next_input)?.0;
+    }
 }
 
 trait Parser<'a, Output> {
@@ -142,6 +748,7 @@ fn match_literal<'a>(expected: &'static str) -> impl Parser<'a, ()> {
 /// combine two parser into a single one returning a tuple result
 fn pair<'a, P1, P2, R1, R2>(parser1: P1, parser2: P2) -> impl Parser<'a, (R1, R2)>
 where
+
     P1: Parser<'a, R1>,
     P2: Parser<'a, R2>,
 {
@@ -205,13 +812,22 @@ where
 // filter right output of a parser pair
 fn right<'a, P1, P2, R1, R2>(parser1: P1, parser2: P2) -> impl Parser<'a, R2>
 where
-
     P1: Parser<'a, R1>,
     P2: Parser<'a, R2>,
 {
     map(pair(parser1, parser2), |(_left, right)| right)
 }
 
+fn optional<'a, P, A>(parser: P) -> impl Parser<'a, Option<A>>
+where
+    P: Parser<'a, A>,
+{
+    move |input| match parser.parse(input) {
+        Ok((next_input, result)) => Ok((next_input, Some(result))),
+        Err(_) => Ok((input, None)),
+    }
+}
+
 fn one_or_more<'a, P, A>(parser: P) -> impl Parser<'a, Vec<A>>
 where
     P: Parser<'a, A>,
@@ -251,7 +867,7 @@ where
     }
 }
 
-fn any_char(input: &str) -> ParseResult<char> {
+fn any_char(input: &str) -> ParseResult<'_, char> {
     match input.chars().next() {
         Some(next) => Ok((&input[next.len_utf8()..], next)),
         _ => Err(input),
@@ -266,6 +882,11 @@ fn not_whitespace_char<'a>() -> impl Parser<'a, char> {
     pred(any_char, |c| !c.is_whitespace())
 }
 
+// whitespace that does not end the current line
+fn blank_char<'a>() -> impl Parser<'a, char> {
+    pred(any_char, |c| *c == ' ' || *c == '\t')
+}
+
 fn whitespace_wrap<'a, P, A>(parser: P) -> impl Parser<'a, A>
 where
     P: Parser<'a, A>,
@@ -273,6 +894,7 @@ where
     right(space0(), left(parser, space0()))
 }
 
+#[allow(dead_code)]
 fn space1<'a>() -> impl Parser<'a, Vec<char>> {
     one_or_more(whitespace_char())
 }
@@ -302,33 +924,249 @@ fn heading_start<'a>() -> impl Parser<'a, Tag> {
 fn nested_list_start<'a>() -> impl Parser<'a, Tag> {
     left(
         one_or_more(any_char.pred(|c| *c == '*')).map(|head| Tag::UnordereList(head.len())),
-        one_or_more(whitespace_char()),
+        one_or_more(blank_char()),
     )
 }
 
 fn flat_list_start<'a>() -> impl Parser<'a, Tag> {
-    pair(any_char.pred(|c| *c == '-'), whitespace_char()).map(|_| Tag::UnordereList(1))
+    left(
+        any_char.pred(|c| *c == '-').map(|_| Tag::UnordereList(1)),
+        one_or_more(blank_char()),
+    )
+}
+
+fn ordered_list_start<'a>() -> impl Parser<'a, (Tag, Option<usize>)> {
+    left(
+        either(
+            one_or_more(any_char.pred(|c| *c == '.')).map(|dots| (OrderedList(dots.len()), None)),
+            explicit_ordinal(),
+        ),
+        one_or_more(blank_char()),
+    )
+}
+
+// Explicit markers nest at the depth of their numbering style
+fn explicit_ordinal<'a>() -> impl Parser<'a, (Tag, Option<usize>)> {
+    let arabic = left(
+        one_or_more(any_char.pred(|c| c.is_ascii_digit())),
+        match_literal("."),
+    )
+    .map(|digits| {
+        let number = digits.into_iter().collect::<String>();
+        (OrderedList(1), number.parse().ok())
+    });
+    let loweralpha = left(
+        any_char.pred(|c| c.is_ascii_lowercase()),
+        match_literal("."),
+    )
+    .map(|letter| (OrderedList(2), Some(letter as usize - 'a' as usize + 1)));
+    let lowerroman = left(
+        one_or_more(any_char.pred(|c| "ivxlcdm".contains(*c))),
+        match_literal(")"),
+    )
+    .map(|numeral| (OrderedList(3), Some(roman_to_number(&numeral))));
+    let upperalpha = left(
+        any_char.pred(|c| c.is_ascii_uppercase()),
+        match_literal("."),
+    )
+    .map(|letter| (OrderedList(4), Some(letter as usize - 'A' as usize + 1)));
+    let upperroman = left(
+        one_or_more(any_char.pred(|c| "IVXLCDM".contains(*c))),
+        match_literal(")"),
+    )
+    .map(|numeral| (OrderedList(5), Some(roman_to_number(&numeral))));
+
+    either(
+        arabic,
+        either(
+            loweralpha,
+            either(lowerroman, either(upperalpha, upperroman)),
+        ),
+    )
+}
+
+fn roman_to_number(numeral: &[char]) -> usize {
+    let value = |c: &char| match c.to_ascii_lowercase() {
+        'i' => 1,
+        'v' => 5,
+        'x' => 10,
+        'l' => 50,
+        'c' => 100,
+        'd' => 500,
+        _ => 1000,
+    };
+
+    // a numeral smaller than the next one is subtracted, as it was already added
+    // it is taken off twice
+    numeral
+        .iter()
+        .map(value)
+        .fold((0, 0), |(total, previous), value| {
+            if value > previous {
+                (total + value - 2 * previous, value)
+            } else {
+                (total + value, value)
+            }
+        })
+        .0
 }
 
 fn list_start<'a>() -> impl Parser<'a, Tag> {
     right(new_line(), either(flat_list_start(), nested_list_start()))
 }
 
+fn list_marker<'a>() -> impl Parser<'a, (Tag, Option<usize>)> {
+    right(
+        zero_or_more(blank_char()),
+        either(
+            either(flat_list_start(), nested_list_start()).map(|tag| (tag, None)),
+            ordered_list_start(),
+        ),
+    )
+}
+
+// `[x]`, `[*]` or `[ ]` turn an unordered list item into a checklist item
+fn checkbox<'a>() -> impl Parser<'a, bool> {
+    left(
+        either(
+            match_literal("[ ]").map(|_| false),
+            either(match_literal("[x]"), match_literal("[*]")).map(|_| true),
+        ),
+        one_or_more(blank_char()),
+    )
+}
+
+// A lone `+` line attaches the next block to the current list item
+fn list_continuation<'a>() -> impl Parser<'a, ()> {
+    right(
+        optional(new_line()),
+        left(
+            line().pred(|line| line.trim_end() == "+"),
+            optional(new_line()),
+        ),
+    )
+    .map(|_| ())
+}
+
 fn list<'a>() -> impl Parser<'a, Element> {
+    either(list_start(), list_marker().map(|(tag, _)| tag)).and_then(|tag| {
+        let checklist = matches!(tag, UnordereList(_));
         pair(
-            either(list_start(), flat_list_start()),
-            zero_or_more(any_char.pred(|c| *c != '\n')).map(|chars| chars.into_iter().collect()),
-        ).map(|(tag, content)| Element {
-        tag,
-        content,
-        children: vec![],
+            optional(checkbox().pred(move |_| checklist)),
+            wrapped_text(),
+        )
+        .map(move |(checkbox, content)| {
+            let mut item = Element {
+                tag: tag.clone(),
+                content,
+                children: vec![],
+                ..Default::default()
+            };
+            if let Some(checked) = checkbox {
+                item.attributes.options.push("checkbox".to_owned());
+                if checked {
+                    item.attributes.options.push("checked".to_owned());
+                }
+            }
+            item
+        })
     })
 }
 
+// The text of a list item or a definition, the lines wrapping it are folded in
+fn wrapped_text<'a>() -> impl Parser<'a, String> {
+    pair(line(), wrapping_lines())
+        .map(|(first, lines)| [vec![first.trim().to_owned()], lines].concat().join("\n"))
+}
+
+// The lines going on with the text above them, without their indentation
+fn wrapping_lines<'a>() -> impl Parser<'a, Vec<String>> {
+    zero_or_more(right(
+        new_line(),
+        line()
+            .pred(|line| continues_paragraph(line))
+            .map(|line| line.trim().to_owned()),
+    ))
+}
+
+// A paragraph goes on until a blank line, a list continuation, attribute lines or
+// the start of another block
+fn continues_paragraph(line: &str) -> bool {
+    let is_block_metadata =
+        block_attribute_line().parse(line).is_ok() || block_anchor_line().parse(line).is_ok();
+    match Tag::next(line) {
+        _ if line.trim().is_empty() || line.trim_end() == "+" || is_block_metadata => false,
+        Paragraph | AttributeEntry(_) | Admonition(_) => true,
+        // an indented line does not start a literal paragraph within a paragraph
+        Literal => line.starts_with([' ', '\t']),
+        _ => false,
+    }
+}
+
+// A `// text` line, unlike the `////` delimiter of a comment block
+fn is_line_comment(line: &str) -> bool {
+    Tag::next(line) == Comment && !line.starts_with("////")
+}
+
+fn line<'a>() -> impl Parser<'a, String> {
+    zero_or_more(any_char.pred(|c| *c != '\n')).map(|chars| chars.into_iter().collect())
+}
+
+/// Parse a description list term, its definition is either on the same line
+/// or the next non blank line
+fn description_item(input: &str) -> ParseResult<'_, Element> {
+    let (next_input, term_line) = right(zero_or_more(blank_char()), line()).parse(input)?;
+    let (term, level, text) = Tag::description_term(&term_line).ok_or(input)?;
+    let mut item = Element {
+        tag: DescriptionList(level),
+        content: term.trim_end().to_owned(),
+        children: vec![],
+        ..Default::default()
+    };
+
+    let mut next_input = next_input;
+    let mut text = text.to_owned();
+    if !text.is_empty() {
+        let (rest, lines) = wrapping_lines().parse(next_input)?;
+        next_input = rest;
+        text = [vec![text.trim().to_owned()], lines].concat().join("\n");
+    } else if list_continuation().parse(next_input).is_err() {
+        let definition_input = blank_lines().parse(next_input)?.0;
+        let is_definition = match Tag::next(definition_input) {
+            Paragraph => true,
+            Literal => definition_input.starts_with([' ', '\t']),
+            _ => false,
+        };
+        if is_definition {
+            let definition =
+                right(zero_or_more(blank_char()), wrapped_text()).parse(definition_input);
+            if let Ok((rest, definition)) = definition {
+                next_input = rest;
+                text = definition;
+            }
+        }
+    }
+
+    if !text.is_empty() {
+        item.children.push(Element {
+            tag: Definition,
+            content: text,
+            children: vec![],
+            ..Default::default()
+        });
+    }
+    Ok((next_input, item))
+}
+
 fn new_line<'a>() -> impl Parser<'a, ()> {
     either(match_literal("\n"), match_literal("\r\n"))
 }
 
+// lines holding only whitespace are blank too
+fn blank_lines<'a>() -> impl Parser<'a, Vec<()>> {
+    zero_or_more(right(zero_or_more(blank_char()), new_line()))
+}
+
 // TODO: factorize this to fn element(Kind) -> impl Parser<Element>
 // If it's possible :)
 fn head<'a>() -> impl Parser<'a, Element> {
@@ -337,97 +1175,739 @@ fn head<'a>() -> impl Parser<'a, Element> {
             heading_start(),
             zero_or_more(any_char.pred(|c| *c != '\n')).map(|chars| chars.into_iter().collect()),
         ),
-        zero_or_more(new_line()),
+        blank_lines(),
     )
     .map(|(tag, content)| Element {
         tag,
         content,
         children: vec![],
+        ..Default::default()
     })
 }
 
-
+/// Parse the lines of a paragraph, keeping the line breaks between them
 fn paragraph<'a>() -> impl Parser<'a, String> {
     pair(
-        not_whitespace_char(),
-        zero_or_more(any_char.pred(|c| *c != '\n')).map(|chars| chars.into_iter().collect()),
+        pair(not_whitespace_char(), line()).map(|(first, rest)| format!("{}{}", first, rest)),
+        zero_or_more(right(
+            new_line(),
+            line().pred(|line| continues_paragraph(line) || is_line_comment(line)),
+        )),
+    )
+    .map(|(first, lines)| {
+        [first]
+            .iter()
+            // the line comments within a paragraph are skipped
+            .chain(lines.iter().filter(|line| !is_line_comment(line)))
+            .map(|line| line.trim_end())
+            .collect::<Vec<&str>>()
+            .join("\n")
+    })
+}
+
+/// Parse a Markdown style quote, the lines prefixed with `>` are parsed as blocks
+/// and an optional `-- author, citetitle` line gives the attribution
+fn markdown_quote(input: &str) -> ParseResult<'_, Element> {
+    let quoted_line = right(
+        match_literal(">"),
+        left(
+            line().pred(|rest| rest.trim_end().is_empty() || rest.starts_with(' ')),
+            optional(new_line()),
+        ),
+    )
+    .map(|rest| rest.strip_prefix(' ').unwrap_or(&rest).to_owned());
+    let attribution = right(match_literal("-- "), left(line(), optional(new_line())));
+    let (next_input, (lines, attribution)) = left(
+        pair(one_or_more(quoted_line), optional(attribution)),
+        blank_lines(),
+    )
+    .parse(input)?;
+
+    let mut quote = Element {
+        tag: Quote,
+        ..Default::default()
+    };
+    let content = lines.join("\n");
+    quote.set_child(parse_elements(&content, 0).map_err(|_| input)?.1);
+    if let Some(attribution) = attribution {
+        let (author, citetitle) = match attribution.split_once(", ") {
+            Some((author, citetitle)) => (author, Some(citetitle)),
+            None => (attribution.as_str(), None),
+        };
+        let credits = &mut quote.attributes.named;
+        credits.push(("attribution".to_owned(), author.trim().to_owned()));
+        credits.extend(citetitle.map(|title| ("citetitle".to_owned(), title.trim().to_owned())));
+    }
+    Ok((next_input, quote))
+}
+
+/// Consecutive indented lines form a literal paragraph, their common indentation
+/// is removed
+fn literal_paragraph(input: &str) -> ParseResult<'_, Element> {
+    let (next_input, lines) = left(
+        one_or_more(left(
+            line().pred(|line| !line.trim().is_empty()),
+            optional(new_line()),
+        )),
+        blank_lines(),
     )
-    .map(|(left, right): (char, String)| format!("{}{}", left, right))
+    .parse(input)?;
+
+    let indent = lines
+        .iter()
+        .map(|line| line.len() - line.trim_start().len())
+        .min()
+        .unwrap_or_default();
+    let content = lines
+        .iter()
+        .map(|line| line[indent..].trim_end())
+        .collect::<Vec<&str>>()
+        .join("\n");
+
+    let literal = Element {
+        tag: Literal,
+        content,
+        ..Default::default()
+    };
+    Ok((next_input, literal))
 }
 
 fn paragraph_element<'a>() -> impl Parser<'a, Element> {
-    left(paragraph(), zero_or_more(new_line())).map(|content| Element {
+    left(paragraph(), blank_lines()).map(|content| Element {
         tag: Paragraph,
         content,
         children: vec![],
+        ..Default::default()
     })
 }
 
-fn either<'a, P1, P2, A>(parser1: P1, parser2: P2) -> impl Parser<'a, A>
-where
-    P1: Parser<'a, A>,
-    P2: Parser<'a, A>,
-{
-    move |input| match parser1.parse(input) {
-        ok @ Ok(_) => ok,
-        Err(_) => parser2.parse(input),
-    }
+fn attribute_name<'a>() -> impl Parser<'a, String> {
+    one_or_more(any_char.pred(|c| c.is_alphanumeric() || *c == '-' || *c == '_'))
+        .map(|chars| chars.into_iter().collect())
 }
 
-#[cfg(test)]
-mod tests {
-    use crate::parser::*;
-    use crate::tree::*;
-    extern crate indoc;
-    use indoc::indoc;
+fn attribute_value<'a>() -> impl Parser<'a, String> {
+    either(
+        quoted_string(),
+        zero_or_more(any_char.pred(|c| *c != ','))
+            .map(|chars| chars.into_iter().collect::<String>().trim().to_owned()),
+    )
+}
 
-    #[test]
-    fn parse_literal() {
-        assert_eq!(
-            Ok((" Once upon a time", ())),
-            match_literal("=").parse("= Once upon a time")
-        );
-        assert_eq!(
-            Ok((" Hello Robert!", ())),
-            match_literal("=").parse("= Hello Robert!")
-        );
-        assert_eq!(
-            Err("Hello Lucas!"),
-            match_literal("=").parse("Hello Lucas!")
-        );
-    }
-    #[test]
-    fn parse_new_line() {
-        assert_eq!(
-            new_line().parse("\nJe suis le ténébreux, le voeuf, l'inconsolé"),
-            Ok(("Je suis le ténébreux, le voeuf, l'inconsolé", ()))
-        );
-    }
-    #[test]
-    fn parse_not_whitespace() {
-        assert_eq!(
-            not_whitespace_char().parse("Je suis le ténébreux, le voeuf, l'inconsolé"),
-            Ok(("e suis le ténébreux, le voeuf, l'inconsolé", 'J'))
-        );
-    }
+fn named_attribute<'a>() -> impl Parser<'a, (String, String)> {
+    pair(
+        left(whitespace_wrap(attribute_name()), match_literal("=")),
+        whitespace_wrap(attribute_value()),
+    )
+}
 
-    #[test]
-    fn parse_heading_start() {
-        assert_eq!(
-            heading_start().parse("=== The return of the king"),
-            Ok(("The return of the king", Heading(3)))
-        );
-        assert_eq!(
-            heading_start().parse("= The fellowship of the ring"),
-            Ok(("The fellowship of the ring", Heading(1)))
-        );
+fn attribute<'a>() -> impl Parser<'a, (Option<String>, String)> {
+    either(
+        named_attribute().map(|(name, value)| (Some(name), value)),
+        whitespace_wrap(attribute_value()).map(|value| (None, value)),
+    )
+}
+
+/// Parse the content of an attribute list, the first positional attribute
+/// may carry `#id`, `.role` and `%option` shorthands after the block style
+fn attribute_list<'a>() -> impl Parser<'a, Attributes> {
+    pair(
+        attribute(),
+        zero_or_more(right(match_literal(","), attribute())),
+    )
+    .map(|(first, rest)| {
+        let mut attributes = Attributes::default();
+        let mut entries = rest;
+        match first {
+            named @ (Some(_), _) => entries.insert(0, named),
+            (None, first) if first.contains(' ') => attributes.positional.push(first),
+            (None, shorthand) => {
+                let markers = shorthand
+                    .match_indices(['#', '.', '%'])
+                    .map(|(index, _)| index)
+                    .collect::<Vec<usize>>();
+                let style = &shorthand[..markers.first().copied().unwrap_or(shorthand.len())];
+                attributes.positional.push(style.trim().to_owned());
+
+                let ends = markers.iter().skip(1).copied().chain([shorthand.len()]);
+                for (start, end) in markers.iter().zip(ends) {
+                    let value = shorthand[start + 1..end].trim().to_owned();
+                    match &shorthand[*start..start + 1] {
+                        "#" => attributes.named.push(("id".to_owned(), value)),
+                        "." => attributes.named.push(("role".to_owned(), value)),
+                        _ => attributes.options.push(value),
+                    }
+                }
+            }
+        }
+
+        for (name, value) in entries {
+            match name {
+                // `options="header,footer"` is the long form of `%header%footer`
+                Some(name) if name == "options" || name == "opts" => attributes
+                    .options
+                    .extend(value.split(',').map(|option| option.trim().to_owned())),
+                Some(name) => attributes.named.push((name, value)),
+                None => attributes.positional.push(value),
+            }
+        }
+        attributes
+    })
+}
+
+fn block_attribute_line<'a>() -> impl Parser<'a, Attributes> {
+    left(
+        right(
+            match_literal("["),
+            one_or_more(any_char.pred(|c| *c != '\n'))
+                .pred(|chars| chars[0] != '[' && chars.last() == Some(&']')),
+        ),
+        blank_lines(),
+    )
+    .map(|chars| {
+        let list = chars[..chars.len() - 1].iter().collect::<String>();
+        let attributes = attribute_list()
+            .parse(&list)
+            .map(|(_, attributes)| attributes);
+        attributes.unwrap_or_default()
+    })
+}
+
+// `.Title` above a block, it is held as the `title` attribute until the block is parsed.
+// `..` and `. ` start literal blocks and list items instead.
+fn block_title_line<'a>() -> impl Parser<'a, Attributes> {
+    left(
+        right(
+            match_literal("."),
+            line().pred(|title| {
+                let title = title.strip_prefix('.').unwrap_or(title);
+                title.starts_with(|c: char| !matches!(c, ' ' | '\t' | '.'))
+            }),
+        ),
+        blank_lines(),
+    )
+    .map(|title| Attributes {
+        named: vec![("title".to_owned(), title.trim_end().to_owned())],
+        ..Default::default()
+    })
+}
+
+// `[[id]]` or `[[id,reftext]]` above a block
+fn block_anchor_line<'a>() -> impl Parser<'a, Attributes> {
+    left(line().pred(|line| anchor(line).is_some()), blank_lines()).map(|line| {
+        let (id, reftext) = anchor(&line).unwrap_or_default();
+        let mut named = vec![("id".to_owned(), id.to_owned())];
+        named.extend(reftext.map(|reftext| ("reftext".to_owned(), reftext.to_owned())));
+        Attributes {
+            named,
+            ..Default::default()
+        }
+    })
+}
+
+// The id of an anchor starts with a letter, `_` or `:` and goes on with word
+// characters, `:`, `.` or `-`
+fn anchor(line: &str) -> Option<(&str, Option<&str>)> {
+    let inner = line.trim_end().strip_prefix("[[")?.strip_suffix("]]")?;
+    let (id, reftext) = match inner.split_once(',') {
+        Some((id, reftext)) => (id, Some(reftext.trim())),
+        None => (inner, None),
+    };
+    let mut chars = id.chars();
+    let first = chars.next()?;
+    let valid = (first.is_alphabetic() || first == '_' || first == ':')
+        && chars.all(|c| c.is_alphanumeric() || matches!(c, '_' | ':' | '.' | '-'));
+    valid.then_some((id, reftext))
+}
+
+// Consecutive attribute, anchor and title lines all apply to the next block
+fn block_attributes<'a>() -> impl Parser<'a, Attributes> {
+    zero_or_more(either(
+        block_attribute_line(),
+        either(block_anchor_line(), block_title_line()),
+    ))
+    .map(|lines| {
+        lines
+            .into_iter()
+            .fold(Attributes::default(), |mut attributes, line| {
+                attributes.merge(line);
+                attributes
+            })
+    })
+}
+
+/// Parse the content of a table written in the given data format, its cells are laid
+/// out in rows of as many cells as there are columns
+fn table(content: &str, format: &str, attributes: &Attributes) -> Element {
+    let content = blank_lines()
+        .parse(content)
+        .map_or(content, |(rest, _)| rest);
+    let lines = content.lines().collect::<Vec<&str>>();
+    let separator = attributes
+        .get("separator")
+        .and_then(|separator| separator.chars().next());
+    let cells = match format {
+        "csv" => delimited_cells(content, separator.unwrap_or(','), true),
+        "tsv" => delimited_cells(content, separator.unwrap_or('\t'), true),
+        "dsv" => delimited_cells(content, separator.unwrap_or(':'), false),
+        _ => table_cells(&lines, separator.unwrap_or('|')),
+    };
+
+    // without `cols` the cells of the first line give the number of columns
+    let mut columns = attributes.get("cols").map(column_specs).unwrap_or_default();
+    if columns.is_empty() {
+        let first_row = cells
+            .iter()
+            .filter(|(line, _)| *line == 0)
+            .map(|(_, cell)| span(cell, "colspan"))
+            .sum::<usize>();
+        columns = vec![(1, Attributes::default()); first_row.max(1)];
+    }
+    let weights = columns
+        .iter()
+        .map(|(weight, _)| *weight)
+        .collect::<Vec<usize>>();
+
+    let mut table = Element {
+        tag: Table,
+        ..Default::default()
+    };
+    // a first line followed by a blank line is an implicit header row
+    let implicit_header = lines.get(1).is_some_and(|line| line.trim().is_empty());
+    if implicit_header && !attributes.has_option("noheader") && !attributes.has_option("header") {
+        table.attributes.options.push("header".to_owned());
+    }
+
+    for (width, (_, attributes)) in column_widths(&weights).into_iter().zip(&columns) {
+        let mut column = Element {
+            tag: TableColumn,
+            attributes: attributes.clone(),
+            ..Default::default()
+        };
+        column
+            .attributes
+            .named
+            .push(("colpcwidth".to_owned(), width));
+        table.children.push(column);
+    }
+    let cells = cells.into_iter().map(|(_, cell)| cell).collect();
+    table.children.extend(table_rows(cells, &columns));
+    table
+}
+
+/// Lay the cells out in rows, the columns covered by a row span of a previous row
+/// are skipped. Cells of an incomplete last row are dropped.
+fn table_rows(cells: Vec<Element>, columns: &[(usize, Attributes)]) -> Vec<Element> {
+    let mut rows = vec![];
+    let mut row = vec![];
+    let mut column = 0;
+    // how many of the next rows are taken by a row span, per column
+    let mut spanned = vec![0usize; columns.len()];
+    let mut spanning = vec![0usize; columns.len()];
+
+    for mut cell in cells {
+        // cells inherit the alignment and style of their column
+        let (_, defaults) = &columns[column];
+        for name in ["halign", "valign"] {
+            if let (None, Some(value)) = (cell.attributes.get(name), defaults.get(name)) {
+                cell.attributes
+                    .named
+                    .push((name.to_owned(), value.to_owned()));
+            }
+        }
+        if cell.attributes.positional.is_empty() {
+            cell.attributes.positional = defaults.positional.clone();
+        }
+        if cell.attributes.style() == Some("asciidoc") {
+            let content = std::mem::take(&mut cell.content);
+            cell.children = parse_elements(&content, 0).map_or(vec![], |(_, blocks)| blocks.0);
+        }
+
+        let colspan = span(&cell, "colspan");
+        let end = (column + colspan).min(columns.len());
+        spanning[column..end].fill(span(&cell, "rowspan") - 1);
+        row.push(cell);
+        column = end;
+
+        loop {
+            while column < columns.len() && spanned[column] > 0 {
+                column += 1;
+            }
+            if column < columns.len() {
+                break;
+            }
+            rows.push(Element {
+                tag: TableRow,
+                children: std::mem::take(&mut row),
+                ..Default::default()
+            });
+            for (spanned, spanning) in spanned.iter_mut().zip(spanning.iter_mut()) {
+                *spanned = spanned.saturating_sub(1).max(std::mem::take(spanning));
+            }
+            column = 0;
+        }
+    }
+    rows
+}
+
+fn span(cell: &Element, name: &str) -> usize {
+    cell.attributes
+        .get(name)
+        .and_then(|span| span.parse().ok())
+        .unwrap_or(1)
+        .max(1)
+}
+
+/// Split the lines of a table into its cells along with the index of the line they
+/// start on, a cell runs until the next separator and may span several lines
+fn table_cells(lines: &[&str], separator: char) -> Vec<(usize, Element)> {
+    let mut cells: Vec<(usize, usize, Element)> = vec![];
+    for (index, line) in lines.iter().enumerate() {
+        let mut fragments = split_cells(line, separator);
+        // the specifier of a cell ends the text before its separator,
+        // right after a whitespace
+        let last = fragments.len() - 1;
+        let specs = fragments[..last]
+            .iter_mut()
+            .enumerate()
+            .map(|(i, fragment)| {
+                // only at the start of a line may a specifier not follow a whitespace,
+                // `|a|b` holds the cells `a` and `b`
+                let start = match fragment.rfind([' ', '\t']) {
+                    Some(space) => space + 1,
+                    None if i == 0 => 0,
+                    None => fragment.len(),
+                };
+                let spec = match cell_spec().parse(&fragment[start..]) {
+                    Ok(("", spec)) if start < fragment.len() => Some(spec),
+                    _ => None,
+                };
+                if spec.is_some() {
+                    fragment.truncate(start);
+                }
+                spec.unwrap_or((1, Attributes::default()))
+            })
+            .collect::<Vec<(usize, Attributes)>>();
+
+        let mut fragments = fragments.into_iter();
+        // text before the first separator belongs to the previous cell
+        let continued = fragments.next().unwrap_or_default();
+        if let Some((_, _, cell)) = cells.last_mut() {
+            cell.content.push('\n');
+            cell.content.push_str(&continued);
+        }
+        for ((duplicates, attributes), text) in specs.into_iter().zip(fragments) {
+            let cell = Element {
+                tag: TableCell,
+                content: text,
+                attributes,
+                ..Default::default()
+            };
+            cells.push((index, duplicates, cell));
+        }
+    }
+
+    // `3*|` repeats a cell
+    cells
+        .into_iter()
+        .flat_map(|(index, duplicates, mut cell)| {
+            cell.content = cell.content.trim().to_owned();
+            vec![(index, cell); duplicates.max(1)]
+        })
+        .collect()
+}
+
+/// Split comma, tab or delimiter separated values into cells along with the index of
+/// the line their record starts on. Quoted csv and tsv values may hold separators,
+/// line breaks and `""` for a quote, dsv values escape the separator with `\\`.
+fn delimited_cells(content: &str, separator: char, quoted: bool) -> Vec<(usize, Element)> {
+    let mut cells = vec![];
+    let mut value = String::new();
+    let mut in_quotes = false;
+    let mut record_values = 0;
+    let mut line = 0;
+    let mut record_line = 0;
+
+    let mut push_value = |record_line: usize, value: &mut String| {
+        let cell = Element {
+            tag: TableCell,
+            content: std::mem::take(value).trim().to_owned(),
+            ..Default::default()
+        };
+        cells.push((record_line, cell));
+    };
+
+    let mut chars = content.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                value.push('"');
+                chars.next();
+            }
+            '"' if in_quotes => in_quotes = false,
+            '"' if quoted && value.trim().is_empty() => {
+                value.clear();
+                in_quotes = true;
+            }
+            '\n' if in_quotes => {
+                value.push(c);
+                line += 1;
+            }
+            // blank lines are skipped
+            '\n' => {
+                if record_values > 0 || !value.trim().is_empty() {
+                    push_value(record_line, &mut value);
+                }
+                value.clear();
+                record_values = 0;
+                line += 1;
+                record_line = line;
+            }
+            '\\' if !quoted && chars.peek() == Some(&separator) => {
+                value.push(separator);
+                chars.next();
+            }
+            _ if c == separator && !in_quotes => {
+                push_value(record_line, &mut value);
+                record_values += 1;
+            }
+            _ => value.push(c),
+        }
+    }
+    if record_values > 0 || !value.trim().is_empty() {
+        push_value(record_line, &mut value);
+    }
+    cells
+}
+
+// `\\|` is a separator character within the cell text
+fn split_cells(line: &str, separator: char) -> Vec<String> {
+    let mut fragments = vec![String::new()];
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if chars.peek() == Some(&separator) => {
+                fragments.last_mut().unwrap().push(separator);
+                chars.next();
+            }
+            _ if c == separator => fragments.push(String::new()),
+            _ => fragments.last_mut().unwrap().push(c),
+        }
+    }
+    fragments
+}
+
+// `[factor*|colspan.rowspan+][halign][.valign][style]`, e.g. `3*`, `2.3+^.>s` or `.2+`
+fn cell_spec<'a>() -> impl Parser<'a, (usize, Attributes)> {
+    let duplication = left(number(), match_literal("*")).map(|factor| (factor, vec![]));
+    let span = left(
+        pair(
+            optional(number()),
+            optional(right(match_literal("."), number())),
+        ),
+        match_literal("+"),
+    )
+    .pred(|(colspan, rowspan)| colspan.is_some() || rowspan.is_some())
+    .map(|(colspan, rowspan)| {
+        let colspan = colspan.map(|span| ("colspan".to_owned(), span.to_string()));
+        let rowspan = rowspan.map(|span| ("rowspan".to_owned(), span.to_string()));
+        (1, colspan.into_iter().chain(rowspan).collect())
+    });
+    pair(
+        optional(either(duplication, span)),
+        pair(alignment(), optional(cell_style())),
+    )
+    .map(|(factor, (alignment, style))| {
+        let (duplicates, mut named) = factor.unwrap_or((1, vec![]));
+        named.extend(alignment);
+        let attributes = Attributes {
+            positional: style.into_iter().collect(),
+            named,
+            ..Default::default()
+        };
+        (duplicates, attributes)
+    })
+}
+
+/// Parse the `cols` attribute into the relative width and attributes of each column,
+/// a single number is the deprecated form of `n*`
+fn column_specs(cols: &str) -> Vec<(usize, Attributes)> {
+    if let Ok(count) = cols.trim().parse::<usize>() {
+        return vec![(1, Attributes::default()); count];
+    }
+    cols.split([',', ';'])
+        .flat_map(|spec| match column_spec().parse(spec.trim()) {
+            Ok(("", (multiplier, column))) => vec![column; multiplier.unwrap_or(1)],
+            _ => vec![(1, Attributes::default())],
+        })
+        .collect()
+}
+
+// `[multiplier*][halign][.valign][width][style]`, e.g. `2*^.>3a`
+fn column_spec<'a>() -> impl Parser<'a, (Option<usize>, (usize, Attributes))> {
+    pair(
+        optional(left(number(), match_literal("*"))),
+        pair(
+            alignment(),
+            pair(
+                optional(left(number(), optional(match_literal("%")))),
+                optional(cell_style()),
+            ),
+        ),
+    )
+    .map(|(multiplier, (alignment, (width, style)))| {
+        let attributes = Attributes {
+            positional: style.into_iter().collect(),
+            named: alignment,
+            ..Default::default()
+        };
+        (multiplier, (width.unwrap_or(1), attributes))
+    })
+}
+
+// `<`, `^` and `>` align the content to the left, center and right, prefixed
+// with a `.` they align it to the top, middle and bottom
+fn alignment<'a>() -> impl Parser<'a, Vec<(String, String)>> {
+    let align = |directions: [&'static str; 3]| {
+        any_char.pred(|c| "<^>".contains(*c)).map(move |c| match c {
+            '<' => directions[0].to_owned(),
+            '^' => directions[1].to_owned(),
+            _ => directions[2].to_owned(),
+        })
+    };
+    pair(
+        optional(align(["left", "center", "right"])),
+        optional(right(
+            match_literal("."),
+            align(["top", "middle", "bottom"]),
+        )),
+    )
+    .map(|(halign, valign)| {
+        let halign = halign.map(|halign| ("halign".to_owned(), halign));
+        let valign = valign.map(|valign| ("valign".to_owned(), valign));
+        halign.into_iter().chain(valign).collect()
+    })
+}
+
+fn cell_style<'a>() -> impl Parser<'a, String> {
+    any_char
+        .map(|c| match c {
+            'a' => "asciidoc",
+            'd' => "default",
+            'e' => "emphasis",
+            'h' => "header",
+            'l' => "literal",
+            'm' => "monospaced",
+            's' => "strong",
+            _ => "",
+        })
+        .pred(|style| !style.is_empty())
+        .map(str::to_owned)
+}
+
+fn number<'a>() -> impl Parser<'a, usize> {
+    one_or_more(any_char.pred(char::is_ascii_digit)).map(|digits| {
+        digits
+            .into_iter()
+            .collect::<String>()
+            .parse()
+            .unwrap_or_default()
+    })
+}
+
+/// Turn the relative widths of the columns into percentages truncated to four decimals,
+/// the last column takes up the remainder
+fn column_widths(weights: &[usize]) -> Vec<String> {
+    let total = weights.iter().sum::<usize>().max(1);
+    // in ten thousandths of a percent
+    let mut widths = weights
+        .iter()
+        .map(|weight| weight * 1_000_000 / total)
+        .collect::<Vec<usize>>();
+    if widths.pop().is_some() {
+        let rest = widths.iter().sum::<usize>();
+        widths.push(1_000_000usize.saturating_sub(rest));
+    }
+    widths
+        .into_iter()
+        .map(|width| match width % 10_000 {
+            0 => (width / 10_000).to_string(),
+            decimals => format!("{}.{:04}", width / 10_000, decimals)
+                .trim_end_matches('0')
+                .to_owned(),
+        })
+        .collect()
+}
+
+fn either<'a, P1, P2, A>(parser1: P1, parser2: P2) -> impl Parser<'a, A>
+where
+    P1: Parser<'a, A>,
+    P2: Parser<'a, A>,
+{
+    move |input| match parser1.parse(input) {
+        ok @ Ok(_) => ok,
+        Err(_) => parser2.parse(input),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::*;
+    use crate::tree::*;
+    extern crate indoc;
+    use indoc::indoc;
+
+    #[test]
+    fn parse_literal() {
+        assert_eq!(
+            Ok((" Once upon a time", ())),
+            match_literal("=").parse("= Once upon a time")
+        );
+        assert_eq!(
+            Ok((" Hello Robert!", ())),
+            match_literal("=").parse("= Hello Robert!")
+        );
+        assert_eq!(
+            Err("Hello Lucas!"),
+            match_literal("=").parse("Hello Lucas!")
+        );
+    }
+    #[test]
+    fn parse_new_line() {
+        assert_eq!(
+            new_line().parse("\nJe suis le ténébreux, le voeuf, l'inconsolé"),
+            Ok(("Je suis le ténébreux, le voeuf, l'inconsolé", ()))
+        );
+    }
+    #[test]
+    fn parse_not_whitespace() {
+        assert_eq!(
+            not_whitespace_char().parse("Je suis le ténébreux, le voeuf, l'inconsolé"),
+            Ok(("e suis le ténébreux, le voeuf, l'inconsolé", 'J'))
+        );
+    }
+
+    #[test]
+    fn parse_heading_start() {
+        assert_eq!(
+            heading_start().parse("=== The return of the king"),
+            Ok(("The return of the king", Heading(3)))
+        );
+        assert_eq!(
+            heading_start().parse("= The fellowship of the ring"),
+            Ok(("The fellowship of the ring", Heading(1)))
+        );
     }
 
     #[test]
     fn parse_paragraph_content() {
         assert_eq!(
             paragraph().parse("Hello Dolly\nJolene Jolen Jolene"),
-            Ok(("\nJolene Jolen Jolene", "Hello Dolly".to_owned()))
+            Ok(("", "Hello Dolly\nJolene Jolen Jolene".to_owned()))
+        );
+        assert_eq!(
+            paragraph().parse("Hello Dolly\n\nJolene Jolen Jolene"),
+            Ok(("\n\nJolene Jolen Jolene", "Hello Dolly".to_owned()))
         );
 
         assert_eq!(
@@ -439,13 +1919,14 @@ mod tests {
     #[test]
     fn parse_paragraph() {
         assert_eq!(
-            paragraph_element().parse("Hello Dolly\nJolene Jolen Jolene"),
+            paragraph_element().parse("Hello Dolly\n\nJolene Jolen Jolene"),
             Ok((
                 "Jolene Jolen Jolene",
                 Element {
                     tag: Paragraph,
                     content: "Hello Dolly".to_owned(),
                     children: vec![],
+                    ..Default::default()
                 }
             ))
         );
@@ -457,6 +1938,7 @@ mod tests {
                     tag: Paragraph,
                     content: "Hello Mike".to_owned(),
                     children: vec![],
+                    ..Default::default()
                 }
             ))
         );
@@ -472,6 +1954,7 @@ mod tests {
                     tag: Heading(1),
                     content: "Hello Dolly".to_owned(),
                     children: vec![],
+                    ..Default::default()
                 }
             ))
         );
@@ -483,6 +1966,7 @@ mod tests {
                     tag: Heading(3),
                     content: "Hello Michel".to_owned(),
                     children: vec![],
+                    ..Default::default()
                 }
             ))
         );
@@ -520,6 +2004,7 @@ mod tests {
                     tag: UnordereList(1),
                     content: "one".to_owned(),
                     children: vec![],
+                    ..Default::default()
                 }
             ))
         );
@@ -527,13 +2012,16 @@ mod tests {
 
     #[test]
     fn single_element() {
-        let expected = Tree {
-            0: vec![Element {
-                tag: Heading(2),
-                content: "The message".to_owned(),
-                children: vec![],
-            }],
-        };
+        let expected = Tree(vec![Element {
+            tag: Heading(2),
+            content: "The message".to_owned(),
+            attributes: Attributes {
+                named: vec![("id".to_owned(), "_the_message".to_owned())],
+                ..Default::default()
+            },
+            children: vec![],
+            ..Default::default()
+        }]);
         assert_eq!(parse("== The message").unwrap().1, expected);
     }
 
@@ -546,25 +2034,30 @@ mod tests {
         == Another title"
         );
 
-        let expected = Tree {
-            0: vec![
-                Element {
-                    tag: Heading(1),
-                    content: "The message".to_owned(),
-                    children: vec![],
-                },
-                Element {
-                    tag: Paragraph,
-                    content: "this is a story that must be told".to_owned(),
-                    children: vec![],
-                },
-                Element {
-                    tag: Heading(2),
-                    content: "Another title".to_owned(),
-                    children: vec![],
+        let expected = Tree(vec![
+            Element {
+                tag: Heading(1),
+                content: "The message".to_owned(),
+                children: vec![],
+                ..Default::default()
+            },
+            Element {
+                tag: Paragraph,
+                content: "this is a story that must be told".to_owned(),
+                children: vec![],
+                ..Default::default()
+            },
+            Element {
+                tag: Heading(2),
+                content: "Another title".to_owned(),
+                attributes: Attributes {
+                    named: vec![("id".to_owned(), "_another_title".to_owned())],
+                    ..Default::default()
                 },
-            ],
-        };
+                children: vec![],
+                ..Default::default()
+            },
+        ]);
         assert_eq!(parse(input).unwrap().1, expected);
     }
 
@@ -572,13 +2065,12 @@ mod tests {
     fn false_heading() {
         assert_eq!(
             parse("=Not a heading").unwrap().1,
-            Tree {
-                0: vec![Element {
-                    tag: Paragraph,
-                    content: "=Not a heading".to_owned(),
-                    children: vec![],
-                }]
-            }
+            Tree(vec![Element {
+                tag: Paragraph,
+                content: "=Not a heading".to_owned(),
+                children: vec![],
+                ..Default::default()
+            }])
         );
     }
 
@@ -599,51 +2091,69 @@ mod tests {
 
         assert_eq!(
             parse(input).unwrap().1,
-            Tree {
-                0: vec![
-                    Element {
-                        tag: Heading(1),
-                        content: "The message".to_owned(),
-                        children: vec![]
-                    },
-                    // Note that any paragraph following H1 considered a "preamble" and not nested into H1 element
-                    Element {
-                        tag: Paragraph,
-                        content: "this is a story that must be told".to_owned(),
-                        children: vec![]
+            Tree(vec![
+                Element {
+                    tag: Heading(1),
+                    content: "The message".to_owned(),
+                    children: vec![],
+                    ..Default::default()
+                },
+                // Note that any paragraph following H1 considered a "preamble" and not nested into H1 element
+                Element {
+                    tag: Paragraph,
+                    content: "this is a story that must be told".to_owned(),
+                    children: vec![],
+                    ..Default::default()
+                },
+                Element {
+                    tag: Heading(2),
+                    content: "Another title".to_owned(),
+                    attributes: Attributes {
+                        named: vec![("id".to_owned(), "_another_title".to_owned())],
+                        ..Default::default()
                     },
-                    Element {
-                        tag: Heading(2),
-                        content: "Another title".to_owned(),
-                        children: vec![
-                            Element {
-                                tag: Paragraph,
+                    children: vec![
+                        Element {
+                            tag: Paragraph,
 
-                                content: "with nested content".to_owned(),
-                                children: vec![],
-                            },
-                            Element {
-                                tag: Heading(3),
-                                content: "And deeper nesting".to_owned(),
-                                children: vec![Element {
-                                    tag: Paragraph,
-                                    content: "with some content".to_owned(),
-                                    children: vec![]
-                                }]
+                            content: "with nested content".to_owned(),
+                            children: vec![],
+                            ..Default::default()
+                        },
+                        Element {
+                            tag: Heading(3),
+                            content: "And deeper nesting".to_owned(),
+                            attributes: Attributes {
+                                named: vec![("id".to_owned(), "_and_deeper_nesting".to_owned())],
+                                ..Default::default()
                             },
-                        ]
-                    },
-                    Element {
-                        tag: Heading(2),
-                        content: "Up a level".to_owned(),
-                        children: vec![Element {
-                            tag: Paragraph,
-                            content: "finally!".to_owned(),
-                            children: vec![]
-                        }]
+                            children: vec![Element {
+                                tag: Paragraph,
+                                content: "with some content".to_owned(),
+                                children: vec![],
+                                ..Default::default()
+                            }],
+                            ..Default::default()
+                        },
+                    ],
+                    ..Default::default()
+                },
+                Element {
+                    tag: Heading(2),
+                    content: "Up a level".to_owned(),
+                    attributes: Attributes {
+                        named: vec![("id".to_owned(), "_up_a_level".to_owned())],
+                        ..Default::default()
                     },
-                ]
-            }
+                    children: vec![Element {
+                        tag: Paragraph,
+                        content: "finally!".to_owned(),
+                        children: vec![],
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                },
+            ])
         )
     }
 
@@ -659,18 +2169,1711 @@ mod tests {
 
         assert_eq!(
             parse(input).unwrap().1,
-            Tree {
-                0: vec![Element {
+            Tree(vec![
+                Element {
                     tag: UnordereList(1),
                     content: "one".to_owned(),
-                    children: vec![]
-                }]
-            }
+                    children: vec![],
+                    ..Default::default()
+                },
+                Element {
+                    tag: UnordereList(1),
+                    content: "two".to_owned(),
+                    children: vec![],
+                    ..Default::default()
+                },
+                Element {
+                    tag: UnordereList(1),
+                    content: "tree".to_owned(),
+                    children: vec![],
+                    ..Default::default()
+                },
+            ])
         );
     }
 
+    #[test]
+    fn parse_deeply_nested_list() {
+        let input = indoc!(
+            "
+            * one
+            ** one.a
+            *** one.a.i
+            ** one.b
+            * two
+
+            - three
+            "
+        );
+
+        assert_eq!(
+            parse(input).unwrap().1,
+            Tree(vec![
+                Element {
+                    tag: UnordereList(1),
+                    content: "one".to_owned(),
+                    children: vec![
+                        Element {
+                            tag: UnordereList(2),
+                            content: "one.a".to_owned(),
+                            children: vec![Element {
+                                tag: UnordereList(3),
+                                content: "one.a.i".to_owned(),
+                                children: vec![],
+                                ..Default::default()
+                            }],
+                            ..Default::default()
+                        },
+                        Element {
+                            tag: UnordereList(2),
+                            content: "one.b".to_owned(),
+                            children: vec![],
+                            ..Default::default()
+                        },
+                    ],
+                    ..Default::default()
+                },
+                Element {
+                    tag: UnordereList(1),
+                    content: "two".to_owned(),
+                    children: vec![],
+                    ..Default::default()
+                },
+                Element {
+                    tag: UnordereList(1),
+                    content: "three".to_owned(),
+                    children: vec![],
+                    ..Default::default()
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn list_ends_on_paragraph() {
+        let input = indoc!(
+            "
+            * one
+            wrapped onto a second line
+
+            Not a list anymore
+            ---
+            "
+        );
+
+        assert_eq!(
+            parse(input).unwrap().1,
+            Tree(vec![
+                Element {
+                    tag: UnordereList(1),
+                    content: "one\nwrapped onto a second line".to_owned(),
+                    children: vec![],
+                    ..Default::default()
+                },
+                Element {
+                    tag: Paragraph,
+                    content: "Not a list anymore".to_owned(),
+                    children: vec![],
+                    ..Default::default()
+                },
+                Element {
+                    tag: ThematicBreak,
+                    ..Default::default()
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn parse_ordered_list() {
+        let input = indoc!(
+            "
+            [%reversed, start=4]
+            . one
+            .. one.a
+            . two
+            "
+        );
+
+        assert_eq!(
+            parse(input).unwrap().1,
+            Tree(vec![
+                Element {
+                    tag: OrderedList(1),
+                    content: "one".to_owned(),
+                    children: vec![Element {
+                        tag: OrderedList(2),
+                        content: "one.a".to_owned(),
+                        children: vec![],
+                        ..Default::default()
+                    }],
+                    attributes: Attributes {
+                        positional: vec!["".to_owned()],
+                        named: vec![("start".to_owned(), "4".to_owned())],
+                        options: vec!["reversed".to_owned()],
+                    },
+                    ..Default::default()
+                },
+                Element {
+                    tag: OrderedList(1),
+                    content: "two".to_owned(),
+                    children: vec![],
+                    ..Default::default()
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn parse_explicit_ordinals() {
+        let input = indoc!(
+            "
+            3. three
+            a. three.a
+            i) three.a.i
+            4. four
+            "
+        );
+
+        assert_eq!(
+            parse(input).unwrap().1,
+            Tree(vec![
+                Element {
+                    tag: OrderedList(1),
+                    content: "three".to_owned(),
+                    children: vec![Element {
+                        tag: OrderedList(2),
+                        content: "three.a".to_owned(),
+                        children: vec![Element {
+                            tag: OrderedList(3),
+                            content: "three.a.i".to_owned(),
+                            children: vec![],
+                            ..Default::default()
+                        }],
+                        ..Default::default()
+                    }],
+                    attributes: Attributes {
+                        named: vec![("start".to_owned(), "3".to_owned())],
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                Element {
+                    tag: OrderedList(1),
+                    content: "four".to_owned(),
+                    children: vec![],
+                    ..Default::default()
+                },
+            ])
+        );
+
+        assert_eq!(
+            explicit_ordinal().parse("iv) four"),
+            Ok((" four", (OrderedList(3), Some(4))))
+        );
+        assert_eq!(
+            explicit_ordinal().parse("IX) nine"),
+            Ok((" nine", (OrderedList(5), Some(9))))
+        );
+        assert_eq!(
+            explicit_ordinal().parse("mcmxciv) year"),
+            Ok((" year", (OrderedList(3), Some(1994))))
+        );
+        let Tree(items) = parse("iv) four\nv) five\n").unwrap().1;
+        assert_eq!(items[0].attributes.get("start"), Some("4"));
+
+        // a first word ending with a multi-byte character is not an ordinal
+        for input in ["Voilà le texte", "Déjà vu"] {
+            let Tree(blocks) = parse(input).unwrap().1;
+            assert_eq!(blocks[0].tag, Paragraph);
+        }
+    }
+
+    #[test]
+    fn parse_mixed_list() {
+        let input = indoc!(
+            "
+            * one
+            . one.1
+            . one.2
+            * two
+            "
+        );
+
+        assert_eq!(
+            parse(input).unwrap().1,
+            Tree(vec![
+                Element {
+                    tag: UnordereList(1),
+                    content: "one".to_owned(),
+                    children: vec![
+                        Element {
+                            tag: OrderedList(1),
+                            content: "one.1".to_owned(),
+                            children: vec![],
+                            ..Default::default()
+                        },
+                        Element {
+                            tag: OrderedList(1),
+                            content: "one.2".to_owned(),
+                            children: vec![],
+                            ..Default::default()
+                        },
+                    ],
+                    ..Default::default()
+                },
+                Element {
+                    tag: UnordereList(1),
+                    content: "two".to_owned(),
+                    children: vec![],
+                    ..Default::default()
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn parse_checklist() {
+        let input = indoc!(
+            "
+            [%interactive]
+            * [x] done
+            * [ ] todo
+            * plain
+            . [x] not a checklist
+            "
+        );
+
+        let options = |options: &[&str]| Attributes {
+            options: options.iter().map(|option| option.to_string()).collect(),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            parse(input).unwrap().1,
+            Tree(vec![
+                Element {
+                    tag: UnordereList(1),
+                    content: "done".to_owned(),
+                    children: vec![],
+                    attributes: Attributes {
+                        positional: vec!["".to_owned()],
+                        ..options(&["checkbox", "checked", "interactive"])
+                    },
+                    ..Default::default()
+                },
+                Element {
+                    tag: UnordereList(1),
+                    content: "todo".to_owned(),
+                    children: vec![],
+                    attributes: options(&["checkbox"]),
+                    ..Default::default()
+                },
+                Element {
+                    tag: UnordereList(1),
+                    content: "plain".to_owned(),
+                    children: vec![Element {
+                        tag: OrderedList(1),
+                        content: "[x] not a checklist".to_owned(),
+                        children: vec![],
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn parse_list_continuation() {
+        let input = indoc!(
+            "
+            * install
+            +
+            run the installer
+            +
+            --
+            first step
+
+            * nested inside the open block
+            --
+            * configure
+            Term::
+            +
+            Attached to the definition
+            "
+        );
+
+        let paragraph = |content: &str| Element {
+            tag: Paragraph,
+            content: content.to_owned(),
+            children: vec![],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            parse(input).unwrap().1,
+            Tree(vec![
+                Element {
+                    tag: UnordereList(1),
+                    content: "install".to_owned(),
+                    children: vec![
+                        paragraph("run the installer"),
+                        Element {
+                            tag: Open,
+                            content: "".to_owned(),
+                            children: vec![
+                                paragraph("first step"),
+                                Element {
+                                    tag: UnordereList(1),
+                                    content: "nested inside the open block".to_owned(),
+                                    children: vec![],
+                                    ..Default::default()
+                                },
+                            ],
+                            ..Default::default()
+                        },
+                    ],
+                    ..Default::default()
+                },
+                Element {
+                    tag: UnordereList(1),
+                    content: "configure".to_owned(),
+                    children: vec![Element {
+                        tag: DescriptionList(1),
+                        content: "Term".to_owned(),
+                        children: vec![Element {
+                            tag: Definition,
+                            content: "".to_owned(),
+                            children: vec![paragraph("Attached to the definition")],
+                            ..Default::default()
+                        }],
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn parse_source_block() {
+        let input = indoc!(
+            "
+            [source,rust]
+            ----
+            fn main() {
+                println!(\"*not bold*\");
+
+            }
+            ----
+            ----
+            $ cargo build
+            ----
+            "
+        );
+
+        assert_eq!(
+            parse(input).unwrap().1,
+            Tree(vec![
+                Element {
+                    tag: Listing,
+                    content: "fn main() {\n    println!(\"*not bold*\");\n\n}".to_owned(),
+                    children: vec![],
+                    attributes: Attributes {
+                        positional: vec!["source".to_owned(), "rust".to_owned()],
+                        named: vec![("language".to_owned(), "rust".to_owned())],
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                Element {
+                    tag: Listing,
+                    content: "$ cargo build".to_owned(),
+                    children: vec![],
+                    ..Default::default()
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn parse_literal_blocks() {
+        let input = indoc!(
+            "
+            ....
+            error: could not compile
+              --> src/main.rs
+            ....
+              $ cargo  build
+                Compiling
+
+            [literal]
+            not *formatted*
+            "
+        );
+
+        assert_eq!(
+            parse(input).unwrap().1,
+            Tree(vec![
+                Element {
+                    tag: Literal,
+                    content: "error: could not compile\n  --> src/main.rs".to_owned(),
+                    children: vec![],
+                    ..Default::default()
+                },
+                Element {
+                    tag: Literal,
+                    content: "$ cargo  build\n  Compiling".to_owned(),
+                    children: vec![],
+                    ..Default::default()
+                },
+                Element {
+                    tag: Literal,
+                    content: "not *formatted*".to_owned(),
+                    children: vec![],
+                    attributes: Attributes {
+                        positional: vec!["literal".to_owned()],
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn parse_containers() {
+        let input = indoc!(
+            "
+            ====
+            An example
+
+            ======
+            * nested example
+            ======
+            ----
+            ****
+            ----
+            ====
+            ****
+            A sidebar
+            ****
+            "
+        );
+
+        assert_eq!(
+            parse(input).unwrap().1,
+            Tree(vec![
+                Element {
+                    tag: Example,
+                    content: "".to_owned(),
+                    children: vec![
+                        Element {
+                            tag: Paragraph,
+                            content: "An example".to_owned(),
+                            children: vec![],
+                            ..Default::default()
+                        },
+                        Element {
+                            tag: Example,
+                            content: "".to_owned(),
+                            children: vec![Element {
+                                tag: UnordereList(1),
+                                content: "nested example".to_owned(),
+                                children: vec![],
+                                ..Default::default()
+                            }],
+                            ..Default::default()
+                        },
+                        Element {
+                            tag: Listing,
+                            content: "****".to_owned(),
+                            children: vec![],
+                            ..Default::default()
+                        },
+                    ],
+                    ..Default::default()
+                },
+                Element {
+                    tag: Sidebar,
+                    content: "".to_owned(),
+                    children: vec![Element {
+                        tag: Paragraph,
+                        content: "A sidebar".to_owned(),
+                        children: vec![],
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn parse_quotes() {
+        let input = indoc!(
+            "
+            [quote, Thomas Jefferson, Papers of Thomas Jefferson]
+            ____
+            Never spend your money before you have it.
+            ____
+            [quote, Abraham Lincoln]
+            Quotation paragraph.
+            > Markdown quote
+            >
+            > on two paragraphs
+            -- Me, Myself
+            [verse, Carl Sandburg]
+            ____
+            The fog comes
+              on little cat feet.
+            ____
+            "
+        );
+
+        let paragraph = |content: &str| Element {
+            tag: Paragraph,
+            content: content.to_owned(),
+            children: vec![],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            parse(input).unwrap().1,
+            Tree(vec![
+                Element {
+                    tag: Quote,
+                    content: "".to_owned(),
+                    children: vec![paragraph("Never spend your money before you have it.")],
+                    attributes: Attributes {
+                        positional: vec![
+                            "quote".to_owned(),
+                            "Thomas Jefferson".to_owned(),
+                            "Papers of Thomas Jefferson".to_owned()
+                        ],
+                        named: named(&[
+                            ("attribution", "Thomas Jefferson"),
+                            ("citetitle", "Papers of Thomas Jefferson")
+                        ]),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                Element {
+                    tag: Quote,
+                    content: "Quotation paragraph.".to_owned(),
+                    children: vec![],
+                    attributes: Attributes {
+                        positional: vec!["quote".to_owned(), "Abraham Lincoln".to_owned()],
+                        named: named(&[("attribution", "Abraham Lincoln")]),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                Element {
+                    tag: Quote,
+                    content: "".to_owned(),
+                    children: vec![paragraph("Markdown quote"), paragraph("on two paragraphs")],
+                    attributes: Attributes {
+                        named: named(&[("attribution", "Me"), ("citetitle", "Myself")]),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                Element {
+                    tag: Verse,
+                    content: "The fog comes\n  on little cat feet.".to_owned(),
+                    children: vec![],
+                    attributes: Attributes {
+                        positional: vec!["verse".to_owned(), "Carl Sandburg".to_owned()],
+                        named: named(&[("attribution", "Carl Sandburg")]),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+            ])
+        );
+
+        let Tree(blocks) = parse(">= 5 is required\n").unwrap().1;
+        assert_eq!(blocks.len(), 1);
+        assert_eq!(blocks[0].tag, Paragraph);
+        assert_eq!(blocks[0].content, ">= 5 is required");
+        assert_eq!(Tag::next(">= 5"), Paragraph);
+    }
+
+    #[test]
+    fn parse_admonitions() {
+        let input = indoc!(
+            "
+            :warning-caption: Watch out
+            NOTE: Remember to bump the version
+
+            [TIP]
+            Paragraph styled as a tip.
+
+            [WARNING]
+            ====
+            Wear a helmet.
+            ====
+            "
+        );
+
+        let attributes = |style: &str| Attributes {
+            positional: vec![style.to_owned()],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            parse(input).unwrap().1,
+            Tree(vec![
+                Element {
+                    tag: AttributeEntry("warning-caption".to_owned()),
+                    content: "Watch out".to_owned(),
+                    ..Default::default()
+                },
+                Element {
+                    tag: Admonition(AdmonitionKind::Note),
+                    content: "Remember to bump the version".to_owned(),
+                    ..Default::default()
+                },
+                Element {
+                    tag: Admonition(AdmonitionKind::Tip),
+                    content: "Paragraph styled as a tip.".to_owned(),
+                    attributes: attributes("TIP"),
+                    ..Default::default()
+                },
+                Element {
+                    tag: Admonition(AdmonitionKind::Warning),
+                    content: "".to_owned(),
+                    children: vec![Element {
+                        tag: Paragraph,
+                        content: "Wear a helmet.".to_owned(),
+                        children: vec![],
+                        ..Default::default()
+                    }],
+                    attributes: Attributes {
+                        named: vec![("caption".to_owned(), "Watch out".to_owned())],
+                        ..attributes("WARNING")
+                    },
+                    ..Default::default()
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn attribute_entry() {
+        assert_eq!(
+            Tag::next(":note-caption: Heads up\n"),
+            AttributeEntry("note-caption".to_owned())
+        );
+        assert_eq!(
+            Tag::next(":!sectids:\n"),
+            AttributeEntry("sectids!".to_owned())
+        );
+        assert_eq!(
+            Tag::attribute_entry(":sectids!:"),
+            Some(("sectids", true, ""))
+        );
+        assert_eq!(Tag::attribute_entry(":not an entry: value"), None);
+    }
+
+    #[test]
+    fn parse_table() {
+        let input = indoc!(
+            "
+            [cols=\"1,2\", options=\"footer\"]
+            |===
+            |Name |Description
+
+            |cargo |Build tool
+            |rustc
+            |Compiler \\| driver
+
+            |Total |2
+            |===
+            "
+        );
+
+        let attributes = |pairs: &[(&str, &str)]| Attributes {
+            named: named(pairs),
+            ..Default::default()
+        };
+        let column = |width: &str| Element {
+            tag: TableColumn,
+            attributes: attributes(&[("colpcwidth", width)]),
+            ..Default::default()
+        };
+        let row = |cells: [&str; 2]| Element {
+            tag: TableRow,
+            children: cells
+                .iter()
+                .map(|text| Element {
+                    tag: TableCell,
+                    content: text.to_string(),
+                    ..Default::default()
+                })
+                .collect(),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            parse(input).unwrap().1,
+            Tree(vec![Element {
+                tag: Table,
+                content: "".to_owned(),
+                children: vec![
+                    column("33.3333"),
+                    column("66.6667"),
+                    row(["Name", "Description"]),
+                    row(["cargo", "Build tool"]),
+                    row(["rustc", "Compiler | driver"]),
+                    row(["Total", "2"]),
+                ],
+                attributes: Attributes {
+                    options: vec!["header".to_owned(), "footer".to_owned()],
+                    ..attributes(&[("cols", "1,2")])
+                },
+                ..Default::default()
+            }])
+        );
+    }
+
+    #[test]
+    fn parse_table_cell_specifiers() {
+        let input = indoc!(
+            "
+            [cols=\"2*,^1\"]
+            |===
+            2+|Spans two columns .2+>.^s|Strong, two rows
+            3*|x
+            |a l|literal
+            a|* item e|em m|mono
+            |===
+            "
+        );
+
+        let cell = |text: &str, style: &[&str], pairs: &[(&str, &str)]| Element {
+            tag: TableCell,
+            content: text.to_owned(),
+            attributes: Attributes {
+                positional: style.iter().map(|style| style.to_string()).collect(),
+                named: named(pairs),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let row = |cells: Vec<Element>| Element {
+            tag: TableRow,
+            children: cells,
+            ..Default::default()
+        };
+        let centered = [("halign", "center")];
+
+        let Tree(blocks) = parse(input).unwrap().1;
+        assert_eq!(
+            blocks[0].children[3..],
+            [
+                row(vec![
+                    cell("Spans two columns", &[], &[("colspan", "2")]),
+                    cell(
+                        "Strong, two rows",
+                        &["strong"],
+                        &[("rowspan", "2"), ("halign", "right"), ("valign", "middle")]
+                    ),
+                ]),
+                row(vec![cell("x", &[], &[]), cell("x", &[], &[])]),
+                row(vec![
+                    cell("x", &[], &[]),
+                    cell("a", &[], &[]),
+                    cell("literal", &["literal"], &centered),
+                ]),
+                row(vec![
+                    Element {
+                        children: vec![Element {
+                            tag: UnordereList(1),
+                            content: "item".to_owned(),
+                            children: vec![],
+                            ..Default::default()
+                        }],
+                        ..cell("", &["asciidoc"], &[])
+                    },
+                    cell("em", &["emphasis"], &[]),
+                    cell("mono", &["monospaced"], &centered),
+                ]),
+            ]
+        );
+
+        // cell text is not read as a specifier when no whitespace precedes it
+        let compact = row(vec![
+            cell("a", &[], &[]),
+            cell("b", &[], &[]),
+            cell("c", &[], &[]),
+        ]);
+        let Tree(blocks) = parse("[cols=\"3*\"]\n|===\n|a|b|c\n|===\n").unwrap().1;
+        assert_eq!(&blocks[0].children[3..], std::slice::from_ref(&compact));
+        let Tree(blocks) = parse("[cols=\"3*\",separator=;]\n|===\n;a;b;c\n|===\n")
+            .unwrap()
+            .1;
+        assert_eq!(blocks[0].children[3..], [compact]);
+    }
+
+    #[test]
+    fn parse_delimited_tables() {
+        let input = indoc!(
+            "
+            ,===
+            Artist,Track
+
+            Bauhaus,\"Bela Lugosi's Dead\"
+            \"Crosby, Stills & Nash\",\"Suite: Judy
+            Blue \"\"Eyes\"\"\"
+            ,===
+
+            [format=dsv, separator=;]
+            |===
+            root;x\\;0
+            |===
+
+            [format=tsv]
+            |===
+            a\tb
+            |===
+            "
+        );
+
+        let rows = |rows: &[&[&str]]| {
+            rows.iter()
+                .map(|cells| Element {
+                    tag: TableRow,
+                    children: cells
+                        .iter()
+                        .map(|text| Element {
+                            tag: TableCell,
+                            content: text.to_string(),
+                            ..Default::default()
+                        })
+                        .collect(),
+                    ..Default::default()
+                })
+                .collect::<Vec<Element>>()
+        };
+
+        let Tree(tables) = parse(input).unwrap().1;
+        assert_eq!(tables.len(), 3);
+        assert_eq!(
+            tables[0].children[2..],
+            rows(&[
+                &["Artist", "Track"],
+                &["Bauhaus", "Bela Lugosi's Dead"],
+                &["Crosby, Stills & Nash", "Suite: Judy\nBlue \"Eyes\""]
+            ])
+        );
+        assert!(tables[0].attributes.has_option("header"));
+        assert_eq!(tables[1].children[2..], rows(&[&["root", "x;0"]]));
+        assert_eq!(tables[2].children[2..], rows(&[&["a", "b"]]));
+    }
+
+    #[test]
+    fn table_columns() {
+        assert_eq!(column_widths(&[1, 1]), ["50", "50"]);
+        assert_eq!(column_widths(&[1, 1, 1]), ["33.3333", "33.3333", "33.3334"]);
+        assert_eq!(column_widths(&[15, 85]), ["15", "85"]);
+
+        let centered = Attributes {
+            positional: vec!["asciidoc".to_owned()],
+            named: vec![
+                ("halign".to_owned(), "center".to_owned()),
+                ("valign".to_owned(), "bottom".to_owned()),
+            ],
+            ..Default::default()
+        };
+        assert_eq!(
+            column_specs("2*^.>3a, 1"),
+            [
+                (3, centered.clone()),
+                (3, centered),
+                (1, Attributes::default())
+            ]
+        );
+        assert_eq!(column_specs("3").len(), 3);
+    }
+
+    #[test]
+    fn parse_breaks() {
+        let input = indoc!(
+            "
+            '''
+            ---
+            - - -
+            * * *
+            ___
+            <<<
+            - item
+            "
+        );
+
+        let tags = parse(input)
+            .unwrap()
+            .1
+            .iter()
+            .map(|element| element.tag.clone())
+            .collect::<Vec<Tag>>();
+        assert_eq!(
+            tags,
+            [
+                ThematicBreak,
+                ThematicBreak,
+                ThematicBreak,
+                ThematicBreak,
+                ThematicBreak,
+                PageBreak,
+                UnordereList(1)
+            ]
+        );
+        assert_eq!(Tag::next("-- -\n"), Paragraph);
+        assert_eq!(Tag::next("* *\n"), UnordereList(1));
+    }
+
+    #[test]
+    fn parse_comments() {
+        let input = indoc!(
+            "
+            // reviewer note
+            * one
+            //
+            * two
+            ////
+            Not published
+
+            either
+            ////
+            Published
+            "
+        );
+
+        let item = |content: &str| Element {
+            tag: UnordereList(1),
+            content: content.to_owned(),
+            children: vec![],
+            ..Default::default()
+        };
+        let comment = |content: &str| Element {
+            tag: Comment,
+            content: content.to_owned(),
+            ..Default::default()
+        };
+        let paragraph = Element {
+            tag: Paragraph,
+            content: "Published".to_owned(),
+            children: vec![],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            parse_with_comments(input).unwrap().1,
+            Tree(vec![
+                comment("reviewer note"),
+                item("one"),
+                comment(""),
+                item("two"),
+                comment("Not published\n\neither"),
+                paragraph.clone(),
+            ])
+        );
+        assert_eq!(
+            parse(input).unwrap().1,
+            Tree(vec![
+                item("one"),
+                Element {
+                    new_list: true,
+                    ..item("two")
+                },
+                paragraph
+            ])
+        );
+    }
+
+    #[test]
+    fn lists_with_attribute_lines() {
+        // a list with its own attribute lines follows the list above instead of joining it
+        for input in ["* a\n\n[square]\n* b", "CPU:: x\n\n[horizontal]\nRAM:: y"] {
+            let Tree(items) = parse(input).unwrap().1;
+            assert_eq!(items.len(), 2, "{:?}", input);
+            assert!(!items[0].new_list && items[1].new_list, "{:?}", input);
+            assert!(items[1].attributes.style().is_some(), "{:?}", input);
+        }
+
+        let Tree(items) = parse(". a\n\n.Steps\n. b").unwrap().1;
+        assert!(items[1].new_list);
+        assert_eq!(items[1].title.as_deref(), Some("Steps"));
+
+        let Tree(items) = parse("* a\n\n[[others]]\n* b").unwrap().1;
+        assert!(items[1].new_list);
+        assert_eq!(items[1].attributes.id(), Some("others"));
+    }
+
+    #[test]
+    fn parse_block_titles() {
+        let input = indoc!(
+            "
+            :table-caption: Tab
+            .Example configuration
+            ====
+            inside
+            ====
+
+            [caption=\"Exhibit A. \"]
+            .Explicit caption
+            ====
+            inside
+            ====
+
+            [title=Data]
+            |===
+            |a
+            |===
+
+            .List title
+            * item
+
+            ====
+            Untitled
+            ====
+
+            .Second example
+            ====
+            inside
+            ====
+            "
+        );
+
+        let Tree(blocks) = parse(input).unwrap().1;
+        let titles = blocks
+            .iter()
+            .map(|block| (block.title.as_deref(), block.attributes.get("caption")))
+            .collect::<Vec<(Option<&str>, Option<&str>)>>();
+        assert_eq!(
+            titles,
+            [
+                (None, None),
+                (Some("Example configuration"), Some("Example 1. ")),
+                (Some("Explicit caption"), Some("Exhibit A. ")),
+                (Some("Data"), Some("Tab 1. ")),
+                (Some("List title"), None),
+                (None, None),
+                (Some("Second example"), Some("Example 2. ")),
+            ]
+        );
+        assert_eq!(Tag::next("..."), Paragraph);
+        assert!(block_title_line().parse(". item").is_err());
+        assert!(block_title_line().parse("....").is_err());
+    }
+
+    #[test]
+    fn parse_anchors() {
+        let input = indoc!(
+            "
+            [[install-guide]]
+            == Install
+
+            [[cargo,Installing with cargo]]
+            Run cargo install.
+
+            [#binaries]
+            Or download a binary.
+
+            [[1st]]
+            "
+        );
+
+        let Tree(blocks) = parse(input).unwrap().1;
+        let section = &blocks[0];
+        assert_eq!(section.attributes.id(), Some("install-guide"));
+        assert_eq!(section.children[0].attributes.id(), Some("cargo"));
+        assert_eq!(
+            section.children[0].attributes.get("reftext"),
+            Some("Installing with cargo")
+        );
+        assert_eq!(section.children[1].attributes.id(), Some("binaries"));
+        // not a valid id, so a paragraph
+        assert_eq!(section.children[2].content, "[[1st]]");
+    }
+
+    #[test]
+    fn section_ids() {
+        let input = indoc!(
+            "
+            == What's new?
+            == What's new?
+            [#_install]
+            == Custom
+            == Install
+            :idprefix:
+            :idseparator: -
+            == Getting _Started_ <b>now</b> &amp; more
+            :sectids!:
+            == No id
+            "
+        );
+
+        fn ids(elements: &[Element]) -> Vec<Option<String>> {
+            elements
+                .iter()
+                .filter(|element| matches!(element.tag, Heading(_)))
+                .flat_map(|element| {
+                    let id = element.attributes.id().map(String::from);
+                    [vec![id], ids(&element.children)].concat()
+                })
+                .collect()
+        }
+
+        let Tree(sections) = parse(input).unwrap().1;
+        assert_eq!(
+            ids(&sections),
+            [
+                Some("_whats_new".to_owned()),
+                Some("_whats_new_2".to_owned()),
+                Some("_install".to_owned()),
+                Some("_install_2".to_owned()),
+                Some("getting-started-now-more".to_owned()),
+                None,
+            ]
+        );
+
+        // unlike the document title, a level 0 section has an id
+        let Tree(sections) = parse("= Title\n\n= Level 0\n\n== Level 1\n").unwrap().1;
+        assert_eq!(
+            ids(&sections),
+            [
+                None,
+                Some("_level_0".to_owned()),
+                Some("_level_1".to_owned())
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_multi_line_paragraphs() {
+        let input = indoc!(
+            "
+            One sentence
+            per line. +
+            :not-an-entry
+            ----
+            listing
+            ----
+            Wrapped
+            // skipped
+             text
+            * item
+
+            Before
+            == Section
+            "
+        );
+        let contents = |elements: &[Element]| {
+            elements
+                .iter()
+                .map(|element| (element.tag.clone(), element.content.clone()))
+                .collect::<Vec<_>>()
+        };
+        let Tree(blocks) = parse(input).unwrap().1;
+        assert_eq!(
+            contents(&blocks),
+            vec![
+                (
+                    Paragraph,
+                    "One sentence\nper line. +\n:not-an-entry".to_owned()
+                ),
+                (Listing, "listing".to_owned()),
+                (Paragraph, "Wrapped\n text".to_owned()),
+                (UnordereList(1), "item".to_owned()),
+                (Paragraph, "Before".to_owned()),
+                (Heading(2), "Section".to_owned()),
+            ]
+        );
+
+        let Tree(blocks) = parse(":hardbreaks-option:\n\nfirst\nsecond").unwrap().1;
+        assert!(blocks[1].attributes.has_option("hardbreaks"));
+
+        // a last line holding only whitespace is not a paragraph
+        for input in ["para\n   ", "\t", "para\r", "para\n\n \r"] {
+            let Tree(blocks) = parse(input).unwrap().1;
+            let contents = contents(&blocks);
+            assert!(contents.len() <= 1, "{:?}", input);
+            assert!(contents
+                .iter()
+                .all(|(tag, text)| *tag == Paragraph && text == "para"));
+        }
+    }
+
+    #[test]
+    fn parse_images() {
+        let input = indoc!(
+            "
+            :imagesdir: images
+
+            .The sunset
+            [#sunset.framed,align=center]
+            image::sunset.jpg[Sunset,300,200,link=https://example.org]
+
+            image::https://example.org/build-status.svg[]
+
+            .Architecture
+            image::/diagrams/system_overview.png[float=right]
+            "
+        );
+        let Tree(blocks) = parse(input).unwrap().1;
+
+        assert_eq!(blocks[1].tag, Image);
+        assert_eq!(blocks[1].content, "images/sunset.jpg");
+        assert_eq!(blocks[1].title.as_deref(), Some("The sunset"));
+        assert_eq!(
+            blocks[1].attributes.named,
+            named(&[
+                ("alt", "Sunset"),
+                ("width", "300"),
+                ("height", "200"),
+                ("link", "https://example.org"),
+                ("id", "sunset"),
+                ("role", "framed"),
+                ("align", "center"),
+                ("caption", "Figure 1. "),
+            ])
+        );
+        assert_eq!(blocks[2].content, "https://example.org/build-status.svg");
+        assert_eq!(
+            blocks[2].attributes.named,
+            named(&[("alt", "build status")])
+        );
+        assert_eq!(blocks[3].content, "/diagrams/system_overview.png");
+        assert_eq!(
+            blocks[3].attributes.named,
+            named(&[
+                ("alt", "system overview"),
+                ("float", "right"),
+                ("caption", "Figure 2. "),
+            ])
+        );
+    }
+
+    #[test]
+    fn parse_media() {
+        let input = indoc!(
+            "
+            :imagesdir: media
+
+            video::intro.mp4[intro.png,640,360,start=10,options=\"autoplay,loop\"]
+
+            video::rPQoq7ThGAU[youtube]
+
+            .Notification
+            audio::ding.ogg[options=nocontrols]
+            "
+        );
+        let Tree(blocks) = parse(input).unwrap().1;
+
+        assert_eq!(blocks[1].tag, Video);
+        assert_eq!(blocks[1].content, "media/intro.mp4");
+        assert_eq!(
+            blocks[1].attributes.named,
+            named(&[
+                ("poster", "intro.png"),
+                ("width", "640"),
+                ("height", "360"),
+                ("start", "10"),
+            ])
+        );
+        assert_eq!(blocks[1].attributes.options, vec!["autoplay", "loop"]);
+        assert_eq!(blocks[2].content, "rPQoq7ThGAU");
+        assert_eq!(blocks[2].attributes.named, named(&[("poster", "youtube")]));
+        assert_eq!(blocks[3].tag, Audio);
+        assert_eq!(blocks[3].content, "media/ding.ogg");
+        assert_eq!(blocks[3].title.as_deref(), Some("Notification"));
+        assert_eq!(blocks[3].attributes.options, vec!["nocontrols"]);
+    }
+
+    #[test]
+    fn parse_discrete_headings() {
+        let input = indoc!(
+            "
+            == Section
+
+            [discrete]
+            === Not a subsection
+
+            still in the section
+
+            ****
+            [float]
+            == Sidebar heading
+
+            sidebar text
+            ****
+            "
+        );
+        let Tree(blocks) = parse(input).unwrap().1;
+        let tags = |elements: &[Element]| {
+            elements
+                .iter()
+                .map(|element| element.tag.clone())
+                .collect::<Vec<Tag>>()
+        };
+
+        assert_eq!(tags(&blocks), vec![Heading(2)]);
+        assert_eq!(
+            tags(&blocks[0].children),
+            vec![DiscreteHeading(3), Paragraph, Sidebar]
+        );
+        let discrete = &blocks[0].children[0];
+        assert_eq!(discrete.content, "Not a subsection");
+        assert_eq!(discrete.attributes.id(), Some("_not_a_subsection"));
+        assert_eq!(
+            tags(&blocks[0].children[2].children),
+            vec![DiscreteHeading(2), Paragraph]
+        );
+    }
+
+    #[test]
+    fn parse_special_sections() {
+        let input = indoc!(
+            "
+            [preface]
+            === Preface
+
+            [appendix]
+            === Installation
+
+            :appendix-caption: Annex
+
+            [appendix]
+            === Configuration
+
+            :appendix-caption!:
+
+            [appendix]
+            === Troubleshooting
+            "
+        );
+        let Tree(blocks) = parse(input).unwrap().1;
+        let sections = blocks
+            .iter()
+            .filter(|block| block.tag == Heading(3))
+            .map(|section| {
+                (
+                    section.attributes.style(),
+                    section.attributes.get("caption"),
+                    section.attributes.id(),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            sections,
+            vec![
+                (Some("preface"), None, Some("_preface")),
+                (
+                    Some("appendix"),
+                    Some("Appendix A: "),
+                    Some("_installation")
+                ),
+                (Some("appendix"), Some("Annex B: "), Some("_configuration")),
+                (Some("appendix"), Some("C. "), Some("_troubleshooting")),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_book() {
+        let input = indoc!(
+            "
+            = The Handbook
+            :doctype: book
+
+            = Getting Started
+
+            What this part is about.
+
+            == Installation
+
+            == Configuration
+
+            = Operations
+
+            [partintro]
+            --
+            Running the service.
+            --
+
+            == Monitoring
+            "
+        );
+        let Tree(blocks) = parse(input).unwrap().1;
+        let tags = |elements: &[Element]| {
+            elements
+                .iter()
+                .map(|element| element.tag.clone())
+                .collect::<Vec<Tag>>()
+        };
+
+        assert_eq!(
+            tags(&blocks),
+            vec![Heading(1), AttributeEntry("doctype".to_owned()), Part, Part]
+        );
+        let (first, second) = (&blocks[2], &blocks[3]);
+        assert_eq!(first.attributes.id(), Some("_getting_started"));
+        assert_eq!(tags(&first.children), vec![Open, Heading(2), Heading(2)]);
+        assert_eq!(first.children[0].attributes.style(), Some("partintro"));
+        assert_eq!(tags(&first.children[0].children), vec![Paragraph]);
+        assert_eq!(tags(&second.children), vec![Open, Heading(2)]);
+        assert_eq!(tags(&second.children[0].children), vec![Paragraph]);
+
+        // the first section after the header keeps its style
+        let input = indoc!(
+            "
+            = The Handbook
+            :doctype: book
+
+            [preface]
+            == Preface
+
+            [appendix]
+            == Glossary
+
+            [appendix]
+            == Changelog
+            "
+        );
+        let Tree(blocks) = parse(input).unwrap().1;
+        assert_eq!(tags(&blocks[2..]), vec![Heading(2), Heading(2), Heading(2)]);
+        assert_eq!(blocks[2].attributes.style(), Some("preface"));
+        assert_eq!(blocks[3].attributes.get("caption"), Some("Appendix A: "));
+        assert_eq!(blocks[4].attributes.get("caption"), Some("Appendix B: "));
+
+        // level 0 sections of an article are not parts
+        let Tree(blocks) = parse("= Article\n\n= Section\n\ntext\n").unwrap().1;
+        assert_eq!(tags(&blocks), vec![Heading(1), Heading(1)]);
+        assert_eq!(tags(&blocks[1].children), vec![Paragraph]);
+    }
+
+    #[test]
+    fn parse_document_title() {
+        // comments, blank lines and block attributes may precede the title
+        for input in [
+            "// license header\n= Doc\n\nPreamble\n\n== S1\nx",
+            "\n= Doc\n\nPreamble\n\n== S1\nx",
+            "[[top]]\n= Doc\n\nPreamble\n\n== S1\nx",
+        ] {
+            let Tree(blocks) = parse(input).unwrap().1;
+            let title = &blocks[0];
+            assert_eq!((&title.tag, title.content.as_str()), (&Heading(1), "Doc"));
+            assert!(title.children.is_empty(), "{:?}", input);
+            assert_eq!(blocks[1].content, "Preamble");
+            assert_eq!(blocks[2].tag, Heading(2));
+            assert_eq!(blocks[2].children[0].content, "x");
+        }
+
+        let Tree(blocks) = parse("[[top]]\n= Doc\n").unwrap().1;
+        assert_eq!(blocks[0].attributes.id(), Some("top"));
+        let Tree(blocks) = parse_with_comments("// license header\n= Doc\n").unwrap().1;
+        assert_eq!(blocks[0].tag, Comment);
+        assert_eq!(blocks[1].tag, Heading(1));
+    }
+
+    #[test]
+    fn parse_delimited_block() {
+        assert_eq!(
+            delimited_block("--\nfirst\n\nsecond\n--\n\nafter"),
+            Ok(("after", "first\n\nsecond"))
+        );
+        assert_eq!(
+            delimited_block("--\nnever closed\n"),
+            Ok(("", "never closed\n"))
+        );
+    }
+
+    #[test]
+    fn parse_description_list() {
+        let input = indoc!(
+            "
+            CPU:: The brain of the computer.
+            Hard drive::
+            Permanent storage.
+            RAM::
+            Memory::
+              Temporary storage.
+              SRAM::: Static
+              DRAM;; Dynamic
+            "
+        );
+
+        let definition = |content: &str| Element {
+            tag: Definition,
+            content: content.to_owned(),
+            children: vec![],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            parse(input).unwrap().1,
+            Tree(vec![
+                Element {
+                    tag: DescriptionList(1),
+                    content: "CPU".to_owned(),
+                    children: vec![definition("The brain of the computer.")],
+                    ..Default::default()
+                },
+                Element {
+                    tag: DescriptionList(1),
+                    content: "Hard drive".to_owned(),
+                    children: vec![definition("Permanent storage.")],
+                    ..Default::default()
+                },
+                Element {
+                    tag: DescriptionList(1),
+                    content: "RAM".to_owned(),
+                    children: vec![],
+                    ..Default::default()
+                },
+                Element {
+                    tag: DescriptionList(1),
+                    content: "Memory".to_owned(),
+                    children: vec![Element {
+                        tag: Definition,
+                        content: "Temporary storage.".to_owned(),
+                        children: vec![Element {
+                            tag: DescriptionList(2),
+                            content: "SRAM".to_owned(),
+                            children: vec![Element {
+                                tag: Definition,
+                                content: "Static".to_owned(),
+                                children: vec![Element {
+                                    tag: DescriptionList(4),
+                                    content: "DRAM".to_owned(),
+                                    children: vec![definition("Dynamic")],
+                                    ..Default::default()
+                                }],
+                                ..Default::default()
+                            }],
+                            ..Default::default()
+                        },],
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                },
+            ])
+        );
+
+        // definitions wrapped over several lines stay in the definition
+        let Tree(items) = parse("CPU:: The brain\nof the computer\nRAM::\nShort term\n  memory\n")
+            .unwrap()
+            .1;
+        assert_eq!(items.len(), 2);
+        assert_eq!(
+            items[0].children,
+            vec![definition("The brain\nof the computer")]
+        );
+        assert_eq!(items[1].children, vec![definition("Short term\nmemory")]);
+    }
+
+    #[test]
+    fn description_term() {
+        assert_eq!(
+            Tag::description_term("std::vec::Vec:: a growable array"),
+            Some(("std::vec::Vec", 1, "a growable array"))
+        );
+        assert_eq!(Tag::description_term("Note: not a term"), None);
+        assert_eq!(Tag::description_term(":: no term"), None);
+    }
+
+    #[test]
+    fn parse_attribute_list() {
+        assert_eq!(
+            block_attribute_line().parse("[loweralpha%reversed, start=\"2\"]\n. one"),
+            Ok((
+                ". one",
+                Attributes {
+                    positional: vec!["loweralpha".to_owned()],
+                    named: vec![("start".to_owned(), "2".to_owned())],
+                    options: vec!["reversed".to_owned()],
+                }
+            ))
+        );
+        assert_eq!(
+            block_attribute_line().parse("[#install.lead.big%collapsible, role=wide]\n"),
+            Ok((
+                "",
+                Attributes {
+                    positional: vec!["".to_owned()],
+                    named: vec![
+                        ("id".to_owned(), "install".to_owned()),
+                        ("role".to_owned(), "lead".to_owned()),
+                        ("role".to_owned(), "big".to_owned()),
+                        ("role".to_owned(), "wide".to_owned()),
+                    ],
+                    options: vec!["collapsible".to_owned()],
+                }
+            ))
+        );
+        assert_eq!(
+            block_attribute_line().parse("[cols=\"1,2\",options=header]\n"),
+            Ok((
+                "",
+                Attributes {
+                    named: vec![("cols".to_owned(), "1,2".to_owned())],
+                    options: vec!["header".to_owned()],
+                    ..Default::default()
+                }
+            ))
+        );
+        assert_eq!(
+            block_attribute_line().parse("[options=\"header,footer\",frame=topbot]\n"),
+            Ok((
+                "",
+                Attributes {
+                    named: vec![("frame".to_owned(), "topbot".to_owned())],
+                    options: vec!["header".to_owned(), "footer".to_owned()],
+                    ..Default::default()
+                }
+            ))
+        );
+        let (_, table) = block("[options=\"header\"]\n|===\n|Name |Value\n|a |b\n|===\n").unwrap();
+        assert!(table.0[0].attributes.has_option("header"));
+        let (_, source) =
+            block("[source#hello,rust,linenums]\n----\nfn main() {}\n----\n").unwrap();
+        assert_eq!(source.0[0].attributes.id(), Some("hello"));
+        assert_eq!(source.0[0].attributes.get("language"), Some("rust"));
+        assert!(source.0[0].attributes.has_option("linenums"));
+        assert_eq!(
+            block_attribute_line().parse("[not an attribute list"),
+            Err("not an attribute list")
+        );
+    }
+
+    #[test]
+    fn parse_preamble_attributes() {
+        let Tree(blocks) = parse("= Doc\n\n[source,rust]\n----\nx\n----\n").unwrap().1;
+        assert_eq!(blocks[1].tag, Listing);
+        assert_eq!(blocks[1].attributes.get("language"), Some("rust"));
+
+        let Tree(blocks) = parse("= Doc\n\n.Ex\n====\nx\n====\n").unwrap().1;
+        assert_eq!(blocks[1].tag, Example);
+        assert_eq!(blocks[1].title.as_deref(), Some("Ex"));
+
+        let Tree(blocks) = parse("= Doc\n\n[#intro.lead]\nText\n").unwrap().1;
+        assert_eq!(blocks[1].tag, Paragraph);
+        assert_eq!(blocks[1].content, "Text");
+        assert_eq!(blocks[1].attributes.id(), Some("intro"));
+
+        let Tree(blocks) = parse("= Doc\n\n[[a1]]\nText\n").unwrap().1;
+        assert_eq!(blocks[1].tag, Paragraph);
+        assert_eq!(blocks[1].attributes.id(), Some("a1"));
+    }
+
     #[test]
     fn eof() {
-        assert_eq!(Tag::next(""), EOF);
+        assert_eq!(Tag::next(""), Eof);
     }
 }
@@ -1,17 +1,104 @@
 
 use crate::tree::Tag::*;
 use core::slice::Iter;
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub struct Element {
     pub tag: Tag,
     pub content: String,
     pub children: Vec<Element>,
+    pub attributes: Attributes,
+    /// Set by a `.Title` line above the block
+    pub title: Option<String>,
+    /// Set on the first item of a list following a list of the same kind it is not
+    /// part of, e.g. when a dropped comment separated them
+    pub new_list: bool,
 }
 
 impl Element {
     pub fn set_child(&mut self, child: Tree) {
         self.children = child.0;
     }
+
+    /// Nest blocks under a list item, description list terms hold them in their definition
+    pub fn attach(&mut self, blocks: Tree) {
+        if let DescriptionList(_) = self.tag {
+            if self.children.is_empty() {
+                self.children.push(Element {
+                    tag: Definition,
+                    ..Default::default()
+                });
+            }
+            self.children[0].children.extend(blocks.0);
+        } else {
+            self.children.extend(blocks.0);
+        }
+    }
+}
+
+/// Attributes set on a block by an attribute list line, e.g. `[loweralpha%reversed, start=4]`
+/// or `[#install.lead%collapsible]`. For lists they are held by the first item.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Attributes {
+    pub positional: Vec<String>,
+    pub named: Vec<(String, String)>,
+    pub options: Vec<String>,
+}
+
+impl Attributes {
+    /// The first positional attribute, also known as the block style
+    pub fn style(&self) -> Option<&str> {
+        self.positional
+            .first()
+            .map(String::as_str)
+            .filter(|style| !style.is_empty())
+    }
+
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.named
+            .iter()
+            .rev()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// The id set by the `#id` shorthand or the `id` attribute
+    pub fn id(&self) -> Option<&str> {
+        self.get("id")
+    }
+
+    /// The roles set by the `.role` shorthand or the `role` attribute
+    pub fn roles(&self) -> impl Iterator<Item = &str> {
+        self.named
+            .iter()
+            .filter(|(name, _)| name == "role")
+            .flat_map(|(_, roles)| roles.split_whitespace())
+    }
+
+    pub fn has_option(&self, option: &str) -> bool {
+        self.options.iter().any(|o| o == option)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        *self == Attributes::default()
+    }
+
+    // Attribute lines stacked on top of the same block are combined
+    pub fn merge(&mut self, other: Attributes) {
+        if !other.positional.is_empty() {
+            self.positional = other.positional;
+        }
+        self.named.extend(other.named);
+        self.options.extend(other.options);
+    }
+}
+
+/// `(name, value)` pairs as held by `Attributes::named`, for the tests
+#[cfg(test)]
+pub fn named(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+    pairs
+        .iter()
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect()
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -23,7 +110,7 @@ impl Tree {
         self.0.push(el);
     }
 
-    pub fn iter(&self) -> Iter<Element> {
+    pub fn iter(&self) -> Iter<'_, Element> {
         self.0.iter()
     }
     pub fn extend(&mut self, rhs: Tree) {
@@ -35,36 +122,169 @@ impl Tree {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub enum Tag {
+    #[default]
     Paragraph,
     UnordereList(usize),
+    OrderedList(usize),
+    DescriptionList(usize),
+    Definition,
+    Open,
+    Example,
+    Sidebar,
+    Quote,
+    Verse,
+    Listing,
+    Literal,
+    Admonition(AdmonitionKind),
+    Table,
+    TableColumn,
+    TableRow,
+    TableCell,
+    ThematicBreak,
+    PageBreak,
+    Image,
+    Video,
+    Audio,
+    Comment,
+    AttributeEntry(String),
     Heading(usize),
-    EOF,
+    /// A `[discrete]` heading, which does not open a section
+    DiscreteHeading(usize),
+    /// A level 0 section of a book, holding its chapters
+    Part,
+    Eof,
 }
 
-impl Tag {
-    pub fn get_depth(&self) -> usize {
-        if let Heading(level) | UnordereList(level) = self {
-            *level
-        } else {
-            panic!("called get_depth on a non nested Tag")
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum AdmonitionKind {
+    #[default]
+    Note,
+    Tip,
+    Important,
+    Caution,
+    Warning,
+}
+
+impl AdmonitionKind {
+    /// The kind matching an admonition label such as `NOTE`, also used as block style
+    pub fn from_label(label: &str) -> Option<Self> {
+        match label {
+            "NOTE" => Some(AdmonitionKind::Note),
+            "TIP" => Some(AdmonitionKind::Tip),
+            "IMPORTANT" => Some(AdmonitionKind::Important),
+            "CAUTION" => Some(AdmonitionKind::Caution),
+            "WARNING" => Some(AdmonitionKind::Warning),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            AdmonitionKind::Note => "note",
+            AdmonitionKind::Tip => "tip",
+            AdmonitionKind::Important => "important",
+            AdmonitionKind::Caution => "caution",
+            AdmonitionKind::Warning => "warning",
         }
     }
 
+    /// The caption used unless overridden by the `<name>-caption` attribute
+    pub fn caption(&self) -> &'static str {
+        match self {
+            AdmonitionKind::Note => "Note",
+            AdmonitionKind::Tip => "Tip",
+            AdmonitionKind::Important => "Important",
+            AdmonitionKind::Caution => "Caution",
+            AdmonitionKind::Warning => "Warning",
+        }
+    }
+}
+
+impl Tag {
+    pub fn is_list_item(&self) -> bool {
+        matches!(self, UnordereList(_) | OrderedList(_) | DescriptionList(_))
+    }
+
     pub fn next(input: &str) -> Self {
-        if let Some(next) = input.chars().nth(0) {
+        // trailing whitespace, e.g. an unterminated blank line, ends the document too
+        let rest = Some(input).filter(|input| !input.trim().is_empty());
+        if let Some(next) = rest.and_then(|input| input.chars().nth(0)) {
+            let line = input.lines().next().unwrap_or_default().trim_end();
+            // list items may be indented
+            let item = input.trim_start_matches([' ', '\t']);
             let head_level = Tag::is_heading(input, 0);
+            let list_level = Tag::is_unordered_list(item, 0);
+            let ordered_level = Tag::is_ordered_list(item);
+            let description_level = Tag::description_term(item).map_or(0, |(_, level, _)| level);
+            // an unset attribute keeps its `!` suffix
+            let attribute_entry = Tag::attribute_entry(line)
+                .map(|(name, unset, _)| format!("{}{}", name, if unset { "!" } else { "" }));
+            let admonition = line
+                .split_once(": ")
+                .and_then(|(label, _)| AdmonitionKind::from_label(label));
+            let block_macro = Tag::block_macro(line).map(|(name, _, _)| name);
             match next {
+                _ if line == "--" => Open,
+                _ if Tag::is_delimiter(line, '-') => Listing,
+                _ if Tag::is_delimiter(line, '.') => Literal,
+                _ if Tag::is_delimiter(line, '=') => Example,
+                _ if Tag::is_delimiter(line, '*') => Sidebar,
+                _ if Tag::is_delimiter(line, '_') => Quote,
+                _ if Tag::is_table_delimiter(line) => Table,
+                // `////` delimits a comment block, `///` is not a line comment
+                _ if Tag::is_delimiter(line, '/') => Comment,
+                '/' if line.starts_with("//") && !line.starts_with("///") => Comment,
+                // `> text` or a bare `>`, `>= 5` is text
+                '>' if line == ">" || line.starts_with("> ") => Quote,
+                ':' if attribute_entry.is_some() => {
+                    AttributeEntry(attribute_entry.unwrap_or_default())
+                }
+                // before the description lists, `image::` is not a term
+                _ if block_macro == Some("image") => Image,
+                _ if block_macro == Some("video") => Video,
+                _ if block_macro == Some("audio") => Audio,
                 '=' if head_level > 0 => Heading(head_level),
-                '-' if head_level == 0 => UnordereList(1),
-                
+                // before the lists, `- - -` and `* * *` are not list items
+                _ if Tag::is_thematic_break(line) => ThematicBreak,
+                '<' if line.len() >= 3 && line.chars().all(|c| c == '<') => PageBreak,
+                _ if list_level > 0 => UnordereList(list_level),
+                _ if ordered_level > 0 => OrderedList(ordered_level),
+                _ if description_level > 0 => DescriptionList(description_level),
+                ' ' | '\t' if !line.trim().is_empty() => Literal,
+                _ if admonition.is_some() => Admonition(admonition.unwrap_or_default()),
                 _ => Paragraph,
             }
         } else {
-            return EOF;
+            Eof
         }
     }
+    // delimiters are at least four characters long, they can be made
+    // longer to nest blocks of the same kind
+    fn is_delimiter(line: &str, delimiter: char) -> bool {
+        line.len() >= 4 && line.chars().all(|c| c == delimiter)
+    }
+
+    // `|===`, or `,===` and `:===` for comma and delimiter separated values,
+    // the `=` can be repeated as for the other delimiters
+    fn is_table_delimiter(line: &str) -> bool {
+        line.strip_prefix(['|', ',', ':'])
+            .is_some_and(|rest| rest.len() >= 3 && rest.chars().all(|c| c == '='))
+    }
+
+    // `'''` or the Markdown `---`, `***` and `___`, whose markers can be evenly spaced
+    // out as in `- - -`
+    fn is_thematic_break(line: &str) -> bool {
+        if line.len() >= 3 && line.chars().all(|c| c == '\'') {
+            return true;
+        }
+        ['-', '*', '_'].iter().any(|marker| {
+            let parts = line.split(*marker).collect::<Vec<&str>>();
+            matches!(parts.as_slice(), ["", gap, same, ""] if gap == same && gap.chars().all(|c| c == ' '))
+        })
+    }
+
     fn is_heading(input: &str, level: usize) -> usize {
         if let Some(next) = input.chars().next() {
             match next {
@@ -80,18 +300,112 @@ impl Tag {
         }
     }
 
+    // `-` only ever marks a flat list, `*` can be repeated to nest items
     fn is_unordered_list(input: &str, level: usize) -> usize {
         if let Some(next) = input.chars().next() {
             match next {
-                '-' | '*' => {
+                '-' if level == 0 => match input[1..].chars().next() {
+                    Some(' ') | Some('\t') => 1,
+                    _ => 0,
+                },
+                '*' => {
                     let level = level + 1;
                     Tag::is_unordered_list(&input[1..input.len()], level)
                 }
-                ' ' => level,
+                ' ' | '\t' => level,
                 _ => 0,
             }
         } else {
             0
         }
     }
+
+    // `.` markers nest by repetition, explicit markers get the depth
+    // matching their numbering style: `1.`, `a.`, `i)`, `A.`, `I)`
+    fn is_ordered_list(input: &str) -> usize {
+        let line = input.lines().next().unwrap_or_default();
+        let marker_len = line.find([' ', '\t']).unwrap_or(0);
+        let marker = &line[..marker_len];
+        let (ordinal, delimiter) = marker
+            .char_indices()
+            .last()
+            .map_or(("", ""), |(last, _)| marker.split_at(last));
+        let is_roman =
+            |lower: &str| !lower.is_empty() && lower.chars().all(|c| "ivxlcdm".contains(c));
+
+        match delimiter {
+            "." if ordinal.chars().all(|c| c == '.') => marker_len,
+            "." if !ordinal.is_empty() && ordinal.chars().all(|c| c.is_ascii_digit()) => 1,
+            "." if ordinal.len() == 1 && ordinal.chars().all(|c| c.is_ascii_lowercase()) => 2,
+            ")" if is_roman(ordinal) => 3,
+            "." if ordinal.len() == 1 && ordinal.chars().all(|c| c.is_ascii_uppercase()) => 4,
+            ")" if is_roman(&ordinal.to_lowercase())
+                && ordinal.chars().all(|c| c.is_ascii_uppercase()) =>
+            {
+                5
+            }
+            _ => 0,
+        }
+    }
+
+    /// Split a description list line into its term, nesting level and inline definition.
+    /// `::`, `:::`, `::::` and `;;` delimit the term at level 1 to 4.
+    pub fn description_term(input: &str) -> Option<(&str, usize, &str)> {
+        let line = &input[..input.find('\n').unwrap_or(input.len())];
+        if line.starts_with(char::is_whitespace) {
+            return None;
+        }
+
+        let bytes = line.as_bytes();
+        let mut position = 1;
+        while position < bytes.len() {
+            let delimiter = bytes[position];
+            let run = bytes[position..]
+                .iter()
+                .take_while(|b| **b == delimiter)
+                .count();
+            let end = position + run;
+            let level = match (delimiter, run) {
+                (b':', 2..=4) => run - 1,
+                (b';', 2) => 4,
+                _ => 0,
+            };
+            if level > 0 && matches!(bytes.get(end), None | Some(b' ' | b'\t' | b'\r')) {
+                return Some((&line[..position], level, line[end..].trim()));
+            }
+            position = end;
+        }
+        None
+    }
+
+    /// Split a document attribute entry line into its name, whether it unsets the
+    /// attribute and its value: `:name: value`, `:name!:` or `:!name:`.
+    pub fn attribute_entry(line: &str) -> Option<(&str, bool, &str)> {
+        let (name, value) = line.strip_prefix(':')?.split_once(':')?;
+        let (name, unset) = match (name.strip_prefix('!'), name.strip_suffix('!')) {
+            (Some(name), _) | (_, Some(name)) => (name, true),
+            _ => (name, false),
+        };
+        let is_name = |c: char| c.is_alphanumeric() || c == '-' || c == '_';
+        if name.is_empty() || !name.chars().all(is_name) {
+            return None;
+        }
+        match value {
+            "" => Some((name, unset, "")),
+            _ if value.starts_with([' ', '\t']) => Some((name, unset, value.trim())),
+            _ => None,
+        }
+    }
+
+    /// Split a block macro line into its name, target and attribute list:
+    /// `image::sunset.jpg[Sunset,300]`.
+    pub fn block_macro(line: &str) -> Option<(&str, &str, &str)> {
+        let (name, rest) = line.split_once("::")?;
+        let (target, attributes) = rest.strip_suffix(']')?.split_once('[')?;
+        let is_name = |c: char| c.is_alphanumeric() || c == '-' || c == '_';
+        if name.is_empty() || !name.chars().all(is_name) || target.contains(char::is_whitespace) {
+            return None;
+        }
+        Some((name, target, attributes))
+    }
 }